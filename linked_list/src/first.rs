@@ -49,7 +49,7 @@ impl List {
         //});
         //self.head = Link::More(new_node);
         let new_node = Box::new(Node {
-            elem: elem,
+            elem,
             next: mem::replace(&mut self.head, Link::Empty), // temporarily replace head of the list with Empty and assign next to the previous head.
         });
         self.head = Link::More(new_node);
@@ -79,6 +79,12 @@ impl List {
     }
 }
 
+impl Default for List {
+    fn default() -> Self {
+        List::new()
+    }
+}
+
 /* The guide claims that the default destructor for our linked list would be recursive
  * and that recursion would be unbounded. This is why we explicitly implement the Drop
  * trait for our list. */
@@ -102,6 +108,48 @@ impl Drop for List {
 
     }
 }
+/* Reverse the list in place. Every node gets unhooked from the front of the old
+ * chain and hooked onto the front of the new one, so nothing is allocated.
+ * Same mem::replace trick as in push/pop, since we can't just move out of a &mut. */
+impl List {
+    pub fn reverse(&mut self) {
+        let mut reversed = Link::Empty;
+        let mut cur_link = mem::replace(&mut self.head, Link::Empty);
+        while let Link::More(mut boxed_node) = cur_link {
+            cur_link = mem::replace(&mut boxed_node.next, reversed);
+            reversed = Link::More(boxed_node);
+        }
+        self.head = reversed;
+    }
+}
+
+/* Move all of other's nodes onto the end of self, leaving other empty.
+ * cur_link is a mutable reference that walks down to the Empty link at the very end
+ * (which is self.head itself when self is empty), and then we just overwrite it. */
+impl List {
+    pub fn append(&mut self, other: &mut List) {
+        let mut cur_link = &mut self.head;
+        while let Link::More(boxed_node) = cur_link {
+            cur_link = &mut boxed_node.next;
+        }
+        *cur_link = mem::replace(&mut other.head, Link::Empty);
+    }
+}
+
+/* Walk the list from tail to head. The links only point forward, so we first
+ * collect a reference to every element (head to tail) and then hand them out backwards. */
+impl List {
+    pub fn iter_rev(&self) -> impl Iterator<Item = &i32> {
+        let mut elems = Vec::new();
+        let mut cur_link = &self.head;
+        while let Link::More(boxed_node) = cur_link {
+            elems.push(&boxed_node.elem);
+            cur_link = &boxed_node.next;
+        }
+        elems.into_iter().rev()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::List;
@@ -133,4 +181,56 @@ mod test {
         assert_eq!(list.pop(), Some(1));
         assert_eq!(list.pop(), None);
     }
+    #[test]
+    fn reverse() {
+        let mut list = List::new();
+        list.reverse();
+        assert_eq!(list.pop(), None);
+
+        list.push(1); list.push(2); list.push(3);
+        list.reverse();
+
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), None);
+    }
+    #[test]
+    fn append() {
+        let mut list = List::new();
+        list.push(2); list.push(1);
+        let mut other = List::new();
+        other.push(4); other.push(3);
+
+        list.append(&mut other);
+        assert_eq!(other.pop(), None);
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(4));
+        assert_eq!(list.pop(), None);
+
+        // appending onto an empty list just takes the other list's nodes
+        let mut empty = List::new();
+        other.push(5);
+        empty.append(&mut other);
+        assert_eq!(other.pop(), None);
+        assert_eq!(empty.pop(), Some(5));
+
+        // and appending an empty list changes nothing
+        list.push(6);
+        list.append(&mut other);
+        assert_eq!(list.pop(), Some(6));
+        assert_eq!(list.pop(), None);
+    }
+    #[test]
+    fn iter_rev() {
+        let mut list = List::new();
+        assert_eq!(list.iter_rev().next(), None);
+
+        list.push(1); list.push(2); list.push(3);
+        assert_eq!(list.iter_rev().collect::<Vec<_>>(), vec![&1, &2, &3]);
+        // the list itself is untouched
+        assert_eq!(list.pop(), Some(3));
+    }
 }
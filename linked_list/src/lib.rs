@@ -1,2 +1,44 @@
 pub mod first;
 pub mod second;
+pub mod third;
+
+/* Helpers that work across the different list implementations. */
+
+/* Compare an owned second::List with a persistent third::List element by element.
+ * Both are walked head to tail, so lists of different lengths are never equal. */
+pub fn lists_equal<T: PartialEq>(a: &second::List<T>, b: &third::List<T>) -> bool {
+    a.iter().eq(b.iter())
+}
+
+/* Move an owned list into a persistent one, reversed: the owned list's tail ends up as
+ * the persistent head. Popping from the front and prepending each element reverses the
+ * order for free, with no buffering. */
+pub fn into_persistent_reversed<T>(list: second::List<T>) -> third::List<T> {
+    list.into_iter().fold(third::List::new(), |persistent, elem| persistent.prepend(elem))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{into_persistent_reversed, lists_equal, second, third};
+    #[test]
+    fn lists_equal_across_modules() {
+        let mut owned = second::List::new();
+        owned.push(1); owned.push(2); owned.push(3);
+        let persistent = third::List::new().prepend(1).prepend(2).prepend(3);
+        assert!(lists_equal(&owned, &persistent));
+
+        let shorter = persistent.tail();
+        assert!(!lists_equal(&owned, &shorter));
+
+        owned.pop();
+        assert!(lists_equal(&owned, &shorter));
+        assert!(!lists_equal(&owned, &persistent));
+    }
+    #[test]
+    fn into_persistent_reversed_order() {
+        let owned = second::List::from_vec(vec![1, 2, 3]);
+        let persistent = into_persistent_reversed(owned);
+        assert_eq!(persistent.head(), Some(&3));
+        assert_eq!(persistent.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+    }
+}
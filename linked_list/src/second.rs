@@ -1,4 +1,11 @@
 /* NOTE: code was initially copied from first.rs */
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::ops::{self, Deref};
 
 /* Adding generics. */
 pub struct List<T> {
@@ -9,10 +16,9 @@ pub struct List<T> {
 // the old version of Link is now reimplemented using Option
 type Link<T> = Option<Box<Node<T>>>;
 
-struct Node<T> {
-    elem: T,
-    next: Link<T>,
-}
+/* Node lives in the raw module below, which is the only place it's public.
+ * Everything in here just sees it as our plain old private node type. */
+use self::raw::Node;
 
 
 impl<T> List<T> {
@@ -23,7 +29,7 @@ impl<T> List<T> {
     pub fn push(&mut self, elem: T) { // I suppose this function does not return anything
 
         let new_node = Box::new(Node {
-            elem: elem,
+            elem,
             next: self.head.take(),
         });
         self.head = Some(new_node);
@@ -67,6 +73,28 @@ impl<T> List<T> {
         })
     }
 
+    /* look n elements past the head without changing anything. peek_nth(0) is just peek. */
+    pub fn peek_nth(&self, n: usize) -> Option<&T> {
+        self.iter().nth(n)
+    }
+
+    /* peek, falling back to default on an empty list. Both borrows share the lifetime 'a,
+     * so the result lives as long as the shorter of the two. */
+    pub fn peek_or<'a>(&'a self, default: &'a T) -> &'a T {
+        self.peek().unwrap_or(default)
+    }
+
+    /* references to the first n elements (or all of them, if there are fewer). */
+    pub fn peek_slice(&self, n: usize) -> Vec<&T> {
+        self.iter().take(n).collect()
+    }
+
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
 }
 
 impl<T> Drop for List<T> {
@@ -86,8 +114,10 @@ impl<T> Drop for List<T> {
 /* This is a tuple struct. We use it here to make a basic wrapper object
  * around our list. */
 pub struct IntoIter<T>(List<T>);
-impl <T> List<T> {
-    pub fn into_iter(self) -> IntoIter<T> {
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> IntoIter<T> {
         IntoIter(self)
     }
 }
@@ -100,87 +130,2376 @@ impl<T> Iterator for IntoIter<T> {
 }
 
 /* add the Iter - &T. */
-pub struct Iter<T> {
-    next: Option<&Node<T>>,
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
 }
 
 impl<T> List<T> {
-    pub fn iter(&self) -> Iter<T> {
-        Iter { next: self.head.map(|node| &node) }
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head.as_deref() }
     }
 }
 
-impl<T> Iterator for Iter<T> {
-    type Item = &T;
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.next.map(|node| {
-            self.next = node.next.map(|node| &node);
+            self.next = node.next.as_deref();
             &node.elem
         })
     }
 }
-#[cfg(test)]
-mod test {
-    use super::List;
-    #[test]
-    fn basics() {
-        let mut list = List::new();
 
-        // Check empty list behaves right
-        assert_eq!(list.pop(), None);
+/* add the IterMut - &mut T.
+ * &mut isn't Copy like & is, so we have to take() the Option out of self.next
+ * instead of just mapping over it. */
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+    /* only filled in once next_back is called, see DoubleEndedIterator below. */
+    buffer: Option<VecDeque<&'a mut T>>,
+}
 
-        // Populate list
-        list.push(1);
-        list.push(2);
-        list.push(3);
+impl<T> List<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { next: self.head.as_deref_mut(), buffer: None }
+    }
+}
 
-        // Check normal removal
-        assert_eq!(list.pop(), Some(3));
-        assert_eq!(list.pop(), Some(2));
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
 
-        // Push some more just to make sure nothing's corrupted
-        list.push(4);
-        list.push(5);
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(buffer) = &mut self.buffer {
+            return buffer.pop_front();
+        }
+        self.next.take().map(|node| {
+            self.next = node.next.as_deref_mut();
+            &mut node.elem
+        })
+    }
+}
 
-        // Check normal removal
-        assert_eq!(list.pop(), Some(5));
-        assert_eq!(list.pop(), Some(4));
+/* We can't walk backwards in a singly linked list, so the first call to next_back
+ * walks the rest of the list and moves a &mut to every remaining element into a
+ * VecDeque (one allocation, O(n) for that call). After that both ends just pop off
+ * the deque. Each &mut is handed out exactly once, so nothing ever aliases. */
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let next = &mut self.next;
+        self.buffer
+            .get_or_insert_with(|| {
+                let mut buffer = VecDeque::new();
+                let mut cur = next.take();
+                while let Some(node) = cur {
+                    cur = node.next.as_deref_mut();
+                    buffer.push_back(&mut node.elem);
+                }
+                buffer
+            })
+            .pop_back()
+    }
+}
 
-        // Check exhaustion
-        assert_eq!(list.pop(), Some(1));
-        assert_eq!(list.pop(), None);
+/* Flatten a list of lists. We walk to the end of the result each time, and then
+ * move the inner list's chain of nodes over, so no nodes get reallocated. */
+impl<T> List<List<T>> {
+    pub fn flatten(mut self) -> List<T> {
+        let mut result = List::new();
+        let mut tail = &mut result.head;
+        while let Some(mut inner) = self.pop() {
+            *tail = inner.head.take();
+            while let Some(node) = tail {
+                tail = &mut node.next;
+            }
+        }
+        result
     }
-    #[test]
-    fn peek() {
-        let mut list = List::new();
-        assert_eq!(list.peek(), None);
-        assert_eq!(list.peek_mut(), None);
-        list.push(1); list.push(2); list.push(3);
+}
 
-        assert_eq!(list.peek(), Some(&3)); // this is interesting...didn't think you could have a reference to a literal. */
-        assert_eq!(list.peek_mut(), Some(&mut 3));
+/* Length helpers. There is no cached length, so len walks the whole list. */
+impl<T> List<T> {
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
 
-        /* This is a test to see if we can actually change the calue of the first element.*/
-        list.peek_mut().map(|value| {
-            *value = 42
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+}
+
+/* An error type for the methods that would rather return a Result than an Option. */
+#[derive(Debug, PartialEq, Eq)]
+pub enum ListError {
+    IndexOutOfBounds { index: usize, len: usize },
+}
+
+impl fmt::Display for ListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ListError::IndexOutOfBounds { index, len } => {
+                write!(f, "index {} is out of bounds for a list of length {}", index, len)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ListError {}
+
+impl<T> List<T> {
+    /* index 0 is the head of the list. */
+    pub fn try_get(&self, index: usize) -> Result<&T, ListError> {
+        self.iter().nth(index).ok_or_else(|| ListError::IndexOutOfBounds {
+            index,
+            len: self.len(),
+        })
+    }
+}
+
+/* Equality and hashing, both element by element from the head.
+ * Hash writes the length first (like slices do) so that lists of lists
+ * don't collide just because their elements line up.
+ *
+ * About Borrow: std already has a blanket impl<T> Borrow<T> for T, so a
+ * HashMap<List<T>, V> can be looked up with a &List<T> out of the box, and
+ * writing our own Borrow<List<T>> would conflict with it.
+ * Borrowing as &[T] (the way Vec<T> does) is not possible, because our
+ * elements are spread across separate heap nodes instead of being contiguous. */
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+impl<T: Hash> Hash for List<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for elem in self.iter() {
+            elem.hash(state);
+        }
+    }
+}
+
+/* Searching by predicate. Indexes count from the head, like try_get.
+ * The list only links forward, so rposition can't start at the back; it scans
+ * the whole thing and remembers the last match it saw. */
+impl<T> List<T> {
+    pub fn position<F: FnMut(&T) -> bool>(&self, pred: F) -> Option<usize> {
+        self.iter().position(pred)
+    }
+
+    pub fn rposition<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Option<usize> {
+        let mut last = None;
+        for (i, elem) in self.iter().enumerate() {
+            if pred(elem) {
+                last = Some(i);
+            }
+        }
+        last
+    }
+
+    /* every index that matches, in ascending order. */
+    pub fn positions<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Vec<usize> {
+        self.iter()
+            .enumerate()
+            .filter(|(_, elem)| pred(elem))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+/* A defensive push that refuses to grow the list past usize::MAX elements.
+ * We don't cache the length, so this walks the list (O(n)) before pushing.
+ * The work happens in try_push_at_len, which takes the length as an argument, so the
+ * tests can claim the list is at the limit without actually building a list that big. */
+impl<T> List<T> {
+    pub fn try_push(&mut self, elem: T) -> Result<(), &'static str> {
+        let len = self.len();
+        self.try_push_at_len(len, elem)
+    }
+
+    fn try_push_at_len(&mut self, len: usize, elem: T) -> Result<(), &'static str> {
+        if len == usize::MAX {
+            return Err("list length would overflow usize");
+        }
+        self.push(elem);
+        Ok(())
+    }
+}
+
+/* Apply f to every element in place, head first. */
+impl<T> List<T> {
+    pub fn for_each_mut<F: FnMut(&mut T)>(&mut self, f: F) {
+        self.iter_mut().for_each(f);
+    }
+}
+
+/* Push elem only if the list is empty, and hand back the head either way.
+ * If there already is a head, elem is simply dropped. */
+impl<T> List<T> {
+    pub fn ensure_head(&mut self, elem: T) -> &mut T {
+        &mut self.head.get_or_insert_with(|| Box::new(Node { elem, next: None })).elem
+    }
+}
+
+/* Split into a front and a back half, reusing the nodes.
+ * The fast pointer moves two nodes for every one step of the slow count, so when
+ * it falls off the end the count is the middle. On odd lengths the front half
+ * gets the extra element. */
+impl<T> List<T> {
+    pub fn split_halves(mut self) -> (List<T>, List<T>) {
+        let mut front_len = 0;
+        let mut fast = self.head.as_deref();
+        while let Some(node) = fast {
+            front_len += 1;
+            fast = node.next.as_deref().and_then(|node| node.next.as_deref());
+        }
+        let back = List { head: self.link_at(front_len).take() };
+        (self, back)
+    }
+
+    /* the Link that comes after the first `at` nodes, or the Empty one at the end
+     * if the list is shorter than that. link_at(0) is the head. */
+    fn link_at(&mut self, at: usize) -> &mut Link<T> {
+        let mut cur_link = &mut self.head;
+        for _ in 0..at {
+            match cur_link {
+                Some(node) => cur_link = &mut node.next,
+                None => break,
+            }
+        }
+        cur_link
+    }
+}
+
+/* iter() behind a trait object, for when you need to store the iterator
+ * somewhere without naming the Iter type. Costs one allocation plus dynamic dispatch. */
+impl<T> List<T> {
+    pub fn boxed_iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(self.iter())
+    }
+}
+
+/* Keyed selection. On ties both of these return the element closest to the head.
+ * Iterator::min_by_key already does that, but Iterator::max_by_key returns the
+ * *last* maximum, so max_by_key keeps the best so far by hand and only replaces it
+ * on a strictly bigger key. */
+impl<T> List<T> {
+    pub fn max_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut f: F) -> Option<&T> {
+        let mut best: Option<(K, &T)> = None;
+        for elem in self.iter() {
+            let key = f(elem);
+            match &best {
+                Some((best_key, _)) if key <= *best_key => {}
+                _ => best = Some((key, elem)),
+            }
+        }
+        best.map(|(_, elem)| elem)
+    }
+
+    pub fn min_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut f: F) -> Option<&T> {
+        self.iter().min_by_key(|elem| f(elem))
+    }
+}
+
+/* Read-only access to the nodes themselves, for people who want to write their own
+ * traversals. Everything here hands out shared references, so the normal List API
+ * can't be broken through it. Node is defined here so second::raw::Node is its only
+ * public path; the fields are pub(super), so the list code can still build and relink
+ * nodes, but nobody outside second can. raw is a child module, so it can see List's
+ * private head. */
+pub mod raw {
+    use super::{Link, List};
+
+    pub struct Node<T> {
+        pub(super) elem: T,
+        pub(super) next: Link<T>,
+    }
+
+    pub fn head_node<T>(list: &List<T>) -> Option<&Node<T>> {
+        list.head.as_deref()
+    }
+
+    impl<T> Node<T> {
+        pub fn elem(&self) -> &T {
+            &self.elem
+        }
+
+        pub fn next(&self) -> Option<&Node<T>> {
+            self.next.as_deref()
+        }
+    }
+}
+
+/* Call f on every overlapping window of `size` elements, head first.
+ * Slices need contiguous memory and our nodes aren't, so we first collect
+ * references to every element into a Vec and borrow the windows out of that.
+ * Nothing is called if the list is shorter than size; size 0 panics, like slice::windows. */
+impl<T> List<T> {
+    pub fn for_each_window<F: FnMut(&[&T])>(&self, size: usize, mut f: F) {
+        let refs: Vec<&T> = self.iter().collect();
+        for window in refs.windows(size) {
+            f(window);
+        }
+    }
+}
+
+/* Pop up to n elements off the front, in pop order (so the old head comes first).
+ * If there are fewer than n, you get all of them and the list ends up empty. */
+impl<T> List<T> {
+    pub fn pop_n(&mut self, n: usize) -> Vec<T> {
+        let mut popped = Vec::new();
+        while popped.len() < n {
+            match self.pop() {
+                Some(elem) => popped.push(elem),
+                None => break,
+            }
+        }
+        popped
+    }
+}
+
+/* Hashing a list walks every node, which adds up if a big list is used as a map key
+ * over and over. freeze() hashes the list once and keeps the result around.
+ * The frozen list only hands out a shared &List (through Deref), so the elements
+ * can't change and the cached hash can't go stale. into_inner thaws it again.
+ *
+ * There is deliberately no Borrow<List<T>>: Borrow requires the borrowed form to hash
+ * exactly like the owner, but a map hashes a &List<T> key with its own hasher over every
+ * element, while our Hash just feeds it the cached u64. Lookups would silently miss. */
+pub struct FrozenList<T> {
+    list: List<T>,
+    hash: u64,
+}
+
+impl<T: Hash> List<T> {
+    pub fn freeze(self) -> FrozenList<T> {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        FrozenList { hash: hasher.finish(), list: self }
+    }
+}
+
+impl<T> FrozenList<T> {
+    pub fn cached_hash(&self) -> u64 {
+        self.hash
+    }
+
+    pub fn into_inner(self) -> List<T> {
+        self.list
+    }
+}
+
+impl<T> Deref for FrozenList<T> {
+    type Target = List<T>;
+    fn deref(&self) -> &List<T> {
+        &self.list
+    }
+}
+
+/* different cached hashes mean different lists, so that check goes first. */
+impl<T: PartialEq> PartialEq for FrozenList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.list == other.list
+    }
+}
+
+impl<T: Eq> Eq for FrozenList<T> {}
+
+impl<T> Hash for FrozenList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+/* Chasing pointers from node to node is slow for read-heavy work, so these convert
+ * to and from a contiguous Vec. Both use head order: v[0] is the head. */
+impl<T> List<T> {
+    pub fn compacted_copy(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    /* throws away whatever the list held before. push prepends, so push back to front. */
+    pub fn rebuild_from_vec(&mut self, v: Vec<T>) {
+        *self = List::new();
+        for elem in v.into_iter().rev() {
+            self.push(elem);
+        }
+    }
+}
+
+/* The owning IntoIter behind a trait object. It owns the list, so it doesn't borrow
+ * anything; the only catch is that a plain Box<dyn Iterator> means 'static, so T
+ * can't hold any borrows either. */
+impl<T: 'static> List<T> {
+    pub fn into_boxed_iter(self) -> Box<dyn Iterator<Item = T>> {
+        Box::new(self.into_iter())
+    }
+}
+
+/* Debug-only sanity check of the list's structure, meant to be sprinkled through tests.
+ * This list has no cached length and no tail pointer (every node is owned by the Box
+ * before it), and len() and is_empty() are computed from the nodes themselves, so they
+ * can't disagree with them. The one invariant this list can break, and only through
+ * unsafe code, is that the chain of nodes actually ends, so that's all we check. */
+#[cfg(debug_assertions)]
+impl<T> List<T> {
+    pub fn verify(&self) {
+        if let Some(index) = self.cycle_entry() {
+            panic!("verify: the list contains a cycle starting at index {}", index);
+        }
+    }
+}
+
+/* Merge any number of sorted lists into one sorted list without cloning anything.
+ * A BinaryHeap holds the current head node of every list; we keep popping the smallest
+ * one, hang it on the end of the result, and push the node after it back in.
+ * That's O(n log k) for n elements spread over k lists.
+ * BinaryHeap is a max-heap, so MergeHead orders itself backwards. Equal elements are
+ * taken from the earlier list first, so the merge is stable. */
+struct MergeHead<T> {
+    node: Box<Node<T>>,
+    source: usize,
+}
+
+impl<T: Ord> Ord for MergeHead<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .node
+            .elem
+            .cmp(&self.node.elem)
+            .then(other.source.cmp(&self.source))
+    }
+}
+
+impl<T: Ord> PartialOrd for MergeHead<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> PartialEq for MergeHead<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Ord> Eq for MergeHead<T> {}
+
+impl<T: Ord> List<T> {
+    pub fn merge_k_sorted<I: IntoIterator<Item = List<T>>>(lists: I) -> List<T> {
+        let mut heap = BinaryHeap::new();
+        for (source, mut list) in lists.into_iter().enumerate() {
+            if let Some(node) = list.head.take() {
+                heap.push(MergeHead { node, source });
+            }
+        }
+
+        let mut merged = List::new();
+        let mut tail = &mut merged.head;
+        while let Some(MergeHead { mut node, source }) = heap.pop() {
+            if let Some(next) = node.next.take() {
+                heap.push(MergeHead { node: next, source });
+            }
+            tail = &mut tail.insert(node).next;
+        }
+        merged
+    }
+}
+
+/* Keep only the first n elements that match pred; everything else (non-matching
+ * elements and any matches after the n-th) is dropped.
+ * retain_nodes is the general one-pass version: it unhooks every node from the old
+ * chain and either hangs it on the end of the new chain or drops it. It returns how
+ * many nodes were dropped. */
+impl<T> List<T> {
+    pub fn keep_first_matching<F: FnMut(&T) -> bool>(&mut self, n: usize, mut pred: F) {
+        let mut kept = 0;
+        self.retain_nodes(|elem| {
+            if kept < n && pred(elem) {
+                kept += 1;
+                true
+            } else {
+                false
+            }
         });
+    }
 
-        assert_eq!(list.peek(), Some(&42));
-        assert_eq!(list.pop(), Some(42));
+    fn retain_nodes<F: FnMut(&T) -> bool>(&mut self, mut keep: F) -> usize {
+        let mut removed = 0;
+        let mut cur_link = self.head.take();
+        let mut tail = &mut self.head;
+        while let Some(mut node) = cur_link {
+            cur_link = node.next.take();
+            if keep(&node.elem) {
+                tail = &mut tail.insert(node).next;
+            } else {
+                removed += 1;
+            }
+        }
+        removed
     }
-    #[test]
-    fn into_iter() {
+}
+
+/* Draw the list as text: head first, each element followed by the arrow, and a
+ * None at the end for the empty link. With " -> " that gives "3 -> 2 -> 1 -> None". */
+impl<T: fmt::Display> List<T> {
+    pub fn render(&self, arrow: &str) -> String {
+        let mut out = String::new();
+        for elem in self.iter() {
+            out.push_str(&elem.to_string());
+            out.push_str(arrow);
+        }
+        out.push_str("None");
+        out
+    }
+}
+
+/* Find the first node equal to target, unhook it and hook it back in as the head.
+ * The node itself is reused, nothing is cloned. Returns whether target was found. */
+impl<T: PartialEq> List<T> {
+    pub fn move_to_front(&mut self, target: &T) -> bool {
+        let index = match self.position(|elem| elem == target) {
+            Some(index) => index,
+            None => return false,
+        };
+        let link = self.link_at(index);
+        if let Some(mut node) = link.take() {
+            *link = node.next.take();
+            node.next = self.head.take();
+            self.head = Some(node);
+        }
+        true
+    }
+}
+
+/* Byte lists, converted to and from plain byte buffers. Byte 0 is the head. */
+impl List<u8> {
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.into_iter().collect()
+    }
+
+    pub fn from_bytes(b: &[u8]) -> List<u8> {
         let mut list = List::new();
-        list.push(1); list.push(2); list.push(3);
+        for &byte in b.iter().rev() {
+            list.push(byte);
+        }
+        list
+    }
+}
 
-        let mut iter = list.into_iter(); // note: value moved here
-        assert_eq!(iter.next(), Some(3));
-        assert_eq!(iter.next(), Some(2));
-        assert_eq!(iter.next(), Some(1));
-        assert_eq!(iter.next(), None);
+/* References to the elements in [start, end), head order, like slicing with list[start..end].
+ * Panics on start > end or end > len, also like slicing. */
+impl<T> List<T> {
+    pub fn range(&self, start: usize, end: usize) -> Vec<&T> {
+        self.check_range(start, end);
+        self.iter().skip(start).take(end - start).collect()
+    }
+
+    fn check_range(&self, start: usize, end: usize) {
+        if start > end {
+            panic!("range start {} is greater than range end {}", start, end);
+        }
+        let len = self.len();
+        if end > len {
+            panic!("range end {} is out of bounds for a list of length {}", end, len);
+        }
+    }
+}
+
+/* Deal the nodes out into two lists by position: even indexes (0, 2, 4, ...) go to the
+ * first list and odd ones to the second. Both keep their original order, and every
+ * node is moved, not copied. */
+impl<T> List<T> {
+    pub fn split_by_index_parity(mut self) -> (List<T>, List<T>) {
+        let mut evens = List::new();
+        let mut odds = List::new();
+        let mut even_tail = &mut evens.head;
+        let mut odd_tail = &mut odds.head;
+        let mut cur_link = self.head.take();
+        let mut index = 0;
+        while let Some(mut node) = cur_link {
+            cur_link = node.next.take();
+            if index % 2 == 0 {
+                even_tail = &mut even_tail.insert(node).next;
+            } else {
+                odd_tail = &mut odd_tail.insert(node).next;
+            }
+            index += 1;
+        }
+        (evens, odds)
     }
+}
+
+/* Unlink every node equal to target in one pass and say how many went away.
+ * (There's no cached length to fix up afterwards.) */
+impl<T: PartialEq> List<T> {
+    pub fn remove_all(&mut self, target: &T) -> usize {
+        self.retain_nodes(|elem| elem != target)
+    }
+}
+
+/* push everything items yields and report how many there were. Each item goes on
+ * the front, so the last one pushed ends up as the head. */
+impl<T> List<T> {
+    pub fn push_all<I: IntoIterator<Item = T>>(&mut self, items: I) -> usize {
+        let mut count = 0;
+        for item in items {
+            self.push(item);
+            count += 1;
+        }
+        count
+    }
+}
 
+/* Run f on the head in place and pass its result back. None if the list is empty,
+ * in which case f is never called. */
+impl<T> List<T> {
+    pub fn with_head_mut<R, F: FnOnce(&mut T) -> R>(&mut self, f: F) -> Option<R> {
+        self.peek_mut().map(f)
+    }
+}
 
+/* Running accumulation, head to tail: entry i is f applied over elements 0..=i,
+ * starting from init. init itself isn't in the output. */
+impl<T> List<T> {
+    pub fn prefix_scan<B: Clone, F: FnMut(&B, &T) -> B>(&self, init: B, mut f: F) -> Vec<B> {
+        let mut acc = init;
+        let mut out = Vec::new();
+        for elem in self.iter() {
+            acc = f(&acc, elem);
+            out.push(acc.clone());
+        }
+        out
+    }
+}
 
+/* Character lists, converted to and from strings. The first char is the head. */
+impl List<char> {
+    pub fn into_string(self) -> String {
+        self.into_iter().collect()
+    }
+
+    pub fn from_chars(s: &str) -> List<char> {
+        let mut list = List::new();
+        for c in s.chars().rev() {
+            list.push(c);
+        }
+        list
+    }
+}
+
+/* Debug-only: the index of the first node that is part of a cycle, or None if the
+ * list ends properly. Safe code can't build a cycle out of Boxes, so this is for
+ * checking code that links nodes together by hand with unsafe. */
+#[cfg(debug_assertions)]
+impl<T> List<T> {
+    pub fn cycle_entry(&self) -> Option<usize> {
+        floyd_entry(self.head.as_deref(), |node| node.next.as_deref(), |a, b| std::ptr::eq(a, b))
+    }
+}
+
+/* Floyd's algorithm over anything with a successor function, so it can be tested on
+ * graphs that are allowed to have cycles (a soundly built Box list never does).
+ * The hare moves two steps for every one of the tortoise. If the hare reaches the end
+ * there's no cycle; otherwise they meet somewhere inside it. Then a pointer walking
+ * from the start and one walking on from the meeting point meet exactly at the entry
+ * of the cycle, and the number of steps taken is its index. */
+#[cfg(debug_assertions)]
+fn floyd_entry<N: Copy>(
+    start: Option<N>,
+    next: impl Fn(N) -> Option<N>,
+    same: impl Fn(N, N) -> bool,
+) -> Option<usize> {
+    let step = |n: Option<N>| n.and_then(&next);
+    let mut slow = start;
+    let mut fast = start;
+    loop {
+        slow = step(slow);
+        fast = step(step(fast));
+        match (slow, fast) {
+            (_, None) => return None,
+            (Some(slow), Some(fast)) if same(slow, fast) => break,
+            _ => {}
+        }
+    }
+
+    let mut index = 0;
+    let mut from_start = start;
+    let mut from_meeting = slow;
+    while let (Some(a), Some(b)) = (from_start, from_meeting) {
+        if same(a, b) {
+            return Some(index);
+        }
+        from_start = step(from_start);
+        from_meeting = step(from_meeting);
+        index += 1;
+    }
+    None
+}
+
+/* Same meaning as slice::partition_point: for a list where pred is true for some prefix
+ * and false after that, the index of the first element where it's false (the length if
+ * it never is). We can't binary search a linked list, so this is a plain linear scan. */
+impl<T> List<T> {
+    pub fn partition_point<F: FnMut(&T) -> bool>(&self, mut pred: F) -> usize {
+        self.iter().take_while(|elem| pred(elem)).count()
+    }
+}
+
+/* Drop every node whose key is the same as the node before it, in one relinking pass
+ * (like Vec::dedup_by_key, only consecutive runs are collapsed). */
+impl<T> List<T> {
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&T) -> K>(&mut self, mut key: F) {
+        let mut prev_key = None;
+        self.retain_nodes(|elem| {
+            let k = key(elem);
+            if prev_key.as_ref() == Some(&k) {
+                false
+            } else {
+                prev_key = Some(k);
+                true
+            }
+        });
+    }
+}
+
+/* Build a list with push_all and check it came out as long as expected.
+ * On a mismatch you still get the list back, along with how long it actually is.
+ * Like push_all, the last item yielded ends up as the head. */
+impl<T> List<T> {
+    pub fn from_iter_checked<I: IntoIterator<Item = T>>(
+        iter: I,
+        expected: usize,
+    ) -> Result<List<T>, (List<T>, usize)> {
+        let mut list = List::new();
+        let actual = list.push_all(iter);
+        if actual == expected {
+            Ok(list)
+        } else {
+            Err((list, actual))
+        }
+    }
+}
+
+/* Build a list that keeps the iterator's order: its first item becomes the head.
+ * Instead of pushing, each new node is hung on the end through a tail cursor. */
+impl<T> List<T> {
+    pub fn from_iter_front<I: IntoIterator<Item = T>>(iter: I) -> List<T> {
+        let mut list = List::new();
+        let mut tail = &mut list.head;
+        for elem in iter {
+            tail = &mut tail.insert(Box::new(Node { elem, next: None })).next;
+        }
+        list
+    }
+}
+
+/* Clone. clone_from is the interesting one: it reuses our existing nodes by overwriting
+ * their elements with the source's, and only allocates if the source is longer.
+ * If the source is shorter, the extra nodes get cut off. We wrap them in a List to drop
+ * them, so they go through our iterative Drop instead of Box's recursive one. */
+impl<T: Clone> Clone for List<T> {
+    fn clone(&self) -> Self {
+        List::from_iter_front(self.iter().cloned())
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        let mut overwritten = 0;
+        for (elem, src) in self.iter_mut().zip(source.iter()) {
+            elem.clone_from(src);
+            overwritten += 1;
+        }
+
+        let mut tail = self.link_at(overwritten);
+        drop(List { head: tail.take() });
+        for elem in source.iter().skip(overwritten) {
+            tail = &mut tail.insert(Box::new(Node { elem: elem.clone(), next: None })).next;
+        }
+    }
+}
+
+/* iter_mut with the position of each element, counting from 0 at the head. */
+impl<T> List<T> {
+    pub fn iter_mut_indexed(&mut self) -> impl Iterator<Item = (usize, &mut T)> {
+        self.iter_mut().enumerate()
+    }
+}
+
+/* Cut off everything before the first sentinel and return it as its own list.
+ * The sentinel and whatever follows stay in self. Without a sentinel the whole
+ * list is taken and self is left empty.
+ * split_front does the cutting: the first `at` nodes move into the returned list. */
+impl<T> List<T> {
+    pub fn take_until(&mut self, sentinel: &T) -> List<T>
+    where
+        T: PartialEq,
+    {
+        let at = match self.position(|elem| elem == sentinel) {
+            Some(index) => index,
+            None => self.len(),
+        };
+        self.split_front(at)
+    }
+
+    fn split_front(&mut self, at: usize) -> List<T> {
+        let rest = self.link_at(at).take();
+        let front = List { head: self.head.take() };
+        self.head = rest;
+        front
+    }
+}
+
+/* Swap the nodes at positions i and j by relinking them, so the elements never move
+ * in memory. Node j is unhooked first (keeping whatever came after it aside), then node i
+ * (keeping the nodes between i and j). Node j goes into i's slot in front of the nodes
+ * that were between them, which leaves the list ending exactly at position j, where
+ * node i goes back in front of the rest. Adjacent nodes just have nothing in between.
+ * Panics if either index is out of bounds, like slice::swap. */
+impl<T> List<T> {
+    pub fn swap_nodes(&mut self, i: usize, j: usize) {
+        let len = self.len();
+        if i >= len || j >= len {
+            panic!(
+                "swap_nodes index out of bounds: the len is {} but the indexes are {} and {}",
+                len, i, j
+            );
+        }
+        let (i, j) = if i < j { (i, j) } else { (j, i) };
+        if i == j {
+            return;
+        }
+
+        let mut node_j = self.link_at(j).take().unwrap();
+        let rest = node_j.next.take();
+
+        let i_link = self.link_at(i);
+        let mut node_i = i_link.take().unwrap();
+        node_j.next = node_i.next.take();
+        *i_link = Some(node_j);
+
+        node_i.next = rest;
+        *self.link_at(j) = Some(node_i);
+    }
+}
+
+/* Pop everything onto the end of an existing Vec, in pop order (head first),
+ * leaving the list empty. */
+impl<T> List<T> {
+    pub fn drain_into(&mut self, out: &mut Vec<T>) {
+        while let Some(elem) = self.pop() {
+            out.push(elem);
+        }
+    }
+}
+
+/* Tail-to-head iteration that only needs a Vec when the list is longer than stack_limit.
+ * An iterator can't pause in the middle of a recursion, so a list of at most stack_limit
+ * elements copies its references into a fixed array of SMALL_REV_LEN (16) slots that
+ * lives inside the iterator, and a longer one collects them into a Vec. Either way it's
+ * one walk, O(len), and either way the iterator itself is boxed, so there's always that
+ * one heap allocation; the short path just doesn't add a second one for the Vec.
+ * The array can't grow, so a stack_limit above 16 panics rather than being quietly
+ * lowered. */
+const SMALL_REV_LEN: usize = 16;
+
+impl<T> List<T> {
+    pub fn iter_rev_smart(&self, stack_limit: usize) -> Box<dyn Iterator<Item = &T> + '_> {
+        if self.rev_fits_inline(stack_limit) {
+            let mut buf: [Option<&T>; SMALL_REV_LEN] = [None; SMALL_REV_LEN];
+            for (slot, elem) in buf.iter_mut().zip(self.iter()) {
+                *slot = Some(elem);
+            }
+            Box::new(IntoIterator::into_iter(buf).rev().flatten())
+        } else {
+            let elems: Vec<&T> = self.iter().collect();
+            Box::new(elems.into_iter().rev())
+        }
+    }
+
+    fn rev_fits_inline(&self, stack_limit: usize) -> bool {
+        assert!(
+            stack_limit <= SMALL_REV_LEN,
+            "stack_limit {} is more than the {} elements iter_rev_smart can keep inline",
+            stack_limit,
+            SMALL_REV_LEN
+        );
+        self.iter().nth(stack_limit).is_none()
+    }
+}
+
+/* How often each distinct element shows up. frequencies_sorted puts the most common
+ * first; elements with the same count are in ascending order. */
+impl<T: Eq + Hash> List<T> {
+    pub fn frequencies(&self) -> HashMap<&T, usize> {
+        let mut counts = HashMap::new();
+        for elem in self.iter() {
+            *counts.entry(elem).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    pub fn frequencies_sorted(&self) -> Vec<(T, usize)>
+    where
+        T: Clone + Ord,
+    {
+        let mut counts: Vec<(T, usize)> = self
+            .frequencies()
+            .into_iter()
+            .map(|(elem, count)| (elem.clone(), count))
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts
+    }
+}
+
+/* Split the list in two at index at: self keeps the first at elements and the rest
+ * comes back as a new list, reusing the nodes. split_off clamps, so an at past the end
+ * just returns an empty list; try_split_off refuses with Err(len) and leaves self alone. */
+impl<T> List<T> {
+    pub fn split_off(&mut self, at: usize) -> List<T> {
+        List { head: self.link_at(at).take() }
+    }
+
+    pub fn try_split_off(&mut self, at: usize) -> Result<List<T>, usize> {
+        let len = self.len();
+        if at > len {
+            Err(len)
+        } else {
+            Ok(self.split_off(at))
+        }
+    }
+}
+
+/* Insert elem where it belongs in an already ascending list: after any equal elements,
+ * in front of the first bigger one. */
+impl<T: Ord> List<T> {
+    pub fn insert_sorted(&mut self, elem: T) {
+        let at = self.partition_point(|x| *x <= elem);
+        let link = self.link_at(at);
+        let next = link.take();
+        *link = Some(Box::new(Node { elem, next }));
+    }
+}
+
+/* Remove duplicates anywhere in the list, keeping the *last* occurrence of each value.
+ * First we count every value (cloned, since the map can't borrow from the list while we
+ * relink it), then while relinking each node uses up one of its value's count, and only
+ * the node that uses up the last one survives. The survivors keep their relative order. */
+impl<T: Eq + Hash + Clone> List<T> {
+    pub fn dedup_keep_last(&mut self) {
+        let mut remaining: HashMap<T, usize> = HashMap::new();
+        for elem in self.iter() {
+            *remaining.entry(elem.clone()).or_insert(0) += 1;
+        }
+        self.retain_nodes(|elem| match remaining.get_mut(elem) {
+            Some(count) => {
+                *count -= 1;
+                *count == 0
+            }
+            None => true,
+        });
+    }
+}
+
+/* Lengths of the runs of neighbouring elements that same() says belong together,
+ * head to tail. same is called on each adjacent pair (previous, current). */
+impl<T> List<T> {
+    pub fn run_lengths<F: FnMut(&T, &T) -> bool>(&self, mut same: F) -> Vec<usize> {
+        let mut runs = Vec::new();
+        let mut prev: Option<&T> = None;
+        for elem in self.iter() {
+            match (prev, runs.last_mut()) {
+                (Some(prev), Some(run)) if same(prev, elem) => *run += 1,
+                _ => runs.push(1),
+            }
+            prev = Some(elem);
+        }
+        runs
+    }
+}
+
+/* Concatenation. append moves all of other's nodes onto our end (same walk as in
+ * first.rs, just with Option), and `a + b` / `a += b` are append for owned lists.
+ * from_vec keeps the Vec's order, so v[0] is the head. */
+impl<T> List<T> {
+    pub fn from_vec(v: Vec<T>) -> List<T> {
+        List::from_iter_front(v)
+    }
+
+    pub fn append(&mut self, other: &mut List<T>) {
+        let mut cur_link = &mut self.head;
+        while let Some(node) = cur_link {
+            cur_link = &mut node.next;
+        }
+        *cur_link = other.head.take();
+    }
+}
+
+impl<T> ops::Add for List<T> {
+    type Output = List<T>;
+    fn add(mut self, mut rhs: List<T>) -> List<T> {
+        self.append(&mut rhs);
+        self
+    }
+}
+
+impl<T> ops::AddAssign for List<T> {
+    fn add_assign(&mut self, mut rhs: List<T>) {
+        self.append(&mut rhs);
+    }
+}
+
+/* Like slice::partition_dedup: collapse every run of consecutive equal elements down to
+ * its first element, but instead of dropping the duplicates, move them into a second
+ * list (in their original order) and return it. Every node ends up in one of the two. */
+impl<T: PartialEq> List<T> {
+    pub fn partition_dedup(&mut self) -> List<T> {
+        let mut dups = List::new();
+        let mut dup_tail = &mut dups.head;
+        let mut cur_link = self.head.take();
+        let mut kept_tail = &mut self.head;
+        while let Some(mut node) = cur_link {
+            cur_link = node.next.take();
+            // move the rest of this node's run over to dups
+            while let Some(mut next) = cur_link.take() {
+                if next.elem != node.elem {
+                    cur_link = Some(next);
+                    break;
+                }
+                cur_link = next.next.take();
+                dup_tail = &mut dup_tail.insert(next).next;
+            }
+            kept_tail = &mut kept_tail.insert(node).next;
+        }
+        dups
+    }
+}
+
+/* iter, but handing out clones of the elements instead of references. */
+impl<T: Clone> List<T> {
+    pub fn iter_cloned(&self) -> impl Iterator<Item = T> + '_ {
+        self.iter().cloned()
+    }
+}
+
+/* Work on the front and back of the list as two separate lists for a while.
+ * We split at `at` (clamped, like split_off), let f loose on the front and g on the
+ * back, then glue the two back together. The closures can push, pop, or do anything
+ * else, and whatever they leave behind is what ends up in self. */
+impl<T> List<T> {
+    pub fn with_split_mut<F, G>(&mut self, at: usize, f: F, g: G)
+    where
+        F: FnOnce(&mut List<T>),
+        G: FnOnce(&mut List<T>),
+    {
+        let mut back = self.split_off(at);
+        f(self);
+        g(&mut back);
+        self.append(&mut back);
+    }
+}
+
+/* Like str::trim_matches: drop elements matching pred from both ends, leaving the
+ * middle alone (matches in the middle stay). The front is just popping; for the back
+ * we find the last element that doesn't match and cut everything after it. */
+impl<T> List<T> {
+    pub fn trim_matches<F: FnMut(&T) -> bool>(&mut self, mut pred: F) {
+        while self.peek().is_some_and(&mut pred) {
+            self.pop();
+        }
+        let keep = self.rposition(|elem| !pred(elem)).map_or(0, |index| index + 1);
+        self.split_off(keep);
+    }
+}
+
+/* A collect() target that keeps the input order: the first item becomes the head.
+ * It's a newtype instead of FromIterator on List itself, so that it's obvious at
+ * the call site which order you're getting. */
+pub struct OrderedList<T>(List<T>);
+
+impl<T> OrderedList<T> {
+    pub fn into_inner(self) -> List<T> {
+        self.0
+    }
+}
+
+impl<T> FromIterator<T> for OrderedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        OrderedList(List::from_iter_front(iter))
+    }
+}
+
+/* Number of distinct elements, counted by collecting references into a HashSet. */
+impl<T: Eq + Hash> List<T> {
+    pub fn count_distinct(&self) -> usize {
+        self.iter().collect::<HashSet<_>>().len()
+    }
+}
+
+/* Set-style push: only push elem if no equal element is in the list yet.
+ * Returns whether it was pushed. O(n), since it has to look at everything first. */
+impl<T: PartialEq> List<T> {
+    pub fn push_if_absent(&mut self, elem: T) -> bool {
+        if self.iter().any(|x| *x == elem) {
+            false
+        } else {
+            self.push(elem);
+            true
+        }
+    }
+}
+
+/* Reverse just the nodes in [start, end), relinking them in place.
+ * We cut off everything from end onwards, then unhook the nodes of the range one by
+ * one and push each in front of what we have so far. Starting "what we have so far"
+ * off as the cut-off rest means the reversed range comes out already attached to it.
+ * Panics on the same bad bounds as range. */
+impl<T> List<T> {
+    pub fn reverse_range(&mut self, start: usize, end: usize) {
+        self.check_range(start, end);
+        let mut reversed = self.link_at(end).take();
+        let start_link = self.link_at(start);
+        let mut cur_link = start_link.take();
+        while let Some(mut node) = cur_link {
+            cur_link = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+        *start_link = reversed;
+    }
+}
+
+/* Consume the list into a sorted Vec. Sorting a linked list in place means lots of
+ * relinking; moving the elements into contiguous memory and using slice::sort is the
+ * usual way out. */
+impl<T: Ord> List<T> {
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut v: Vec<T> = self.into_iter().collect();
+        v.sort();
+        v
+    }
+}
+
+/* Whether the head equals other, without popping it. An empty list has no head to match. */
+impl<T: PartialEq> List<T> {
+    pub fn head_equals(&self, other: &T) -> bool {
+        self.peek() == Some(other)
+    }
+}
+
+/* Keep popping the head while pred holds for it, and return what was popped
+ * (in pop order). Stops at the first head that fails, which stays in the list. */
+impl<T> List<T> {
+    pub fn pop_while<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> Vec<T> {
+        let mut popped = Vec::new();
+        while self.peek().is_some_and(&mut pred) {
+            popped.extend(self.pop());
+        }
+        popped
+    }
+}
+
+/* Like Iterator::nth on the list itself: pop and drop the first n elements, then pop
+ * and return the next one. Note that a list with n or fewer elements still gets
+ * drained completely before you get None back. */
+impl<T> List<T> {
+    pub fn take_nth(&mut self, n: usize) -> Option<T> {
+        for _ in 0..n {
+            self.pop()?;
+        }
+        self.pop()
+    }
+}
+
+/* Set operations. These treat both lists as sets: duplicates are ignored and every
+ * value shows up at most once in the result. The results are new lists of clones,
+ * with self's elements first (in self's order), then other's. */
+impl<T: Eq + Hash + Clone> List<T> {
+    pub fn symmetric_difference(&self, other: &List<T>) -> List<T> {
+        let ours: HashSet<&T> = self.iter().collect();
+        let theirs: HashSet<&T> = other.iter().collect();
+        let mut seen = HashSet::new();
+        let only_ours = self.iter().filter(|elem| !theirs.contains(elem));
+        let only_theirs = other.iter().filter(|elem| !ours.contains(elem));
+        let unique = only_ours.chain(only_theirs).filter(|elem| seen.insert(*elem));
+        List::from_iter_front(unique.cloned())
+    }
+
+    pub fn intersection(&self, other: &List<T>) -> List<T> {
+        let theirs: HashSet<&T> = other.iter().collect();
+        let mut seen = HashSet::new();
+        let common = self
+            .iter()
+            .filter(|elem| theirs.contains(elem))
+            .filter(|elem| seen.insert(*elem));
+        List::from_iter_front(common.cloned())
+    }
+
+    pub fn union(&self, other: &List<T>) -> List<T> {
+        let mut seen = HashSet::new();
+        let all = self.iter().chain(other.iter()).filter(|elem| seen.insert(*elem));
+        List::from_iter_front(all.cloned())
+    }
+}
+
+/* Walk two lists side by side, head to tail, stopping when the shorter one runs out. */
+impl<T> List<T> {
+    pub fn zip<'a, U>(&'a self, other: &'a List<U>) -> impl Iterator<Item = (&'a T, &'a U)> {
+        self.iter().zip(other.iter())
+    }
+}
+
+/* For places that expect at most one element: Ok(None) when empty, Ok(Some(head))
+ * for exactly one element, and Err(len) when there are more. */
+impl<T> List<T> {
+    pub fn single(&self) -> Result<Option<&T>, usize> {
+        match self.len() {
+            0 | 1 => Ok(self.peek()),
+            len => Err(len),
+        }
+    }
+}
+
+/* fold, but f gets each element mutably, so one pass can both change the elements
+ * and build up a result. */
+impl<T> List<T> {
+    pub fn fold_mut<B, F: FnMut(B, &mut T) -> B>(&mut self, init: B, f: F) -> B {
+        self.iter_mut().fold(init, f)
+    }
+}
+
+/* Cut the first n nodes (or all of them, if there are fewer) off into their own list,
+ * in their original order. Unlike pop_n the nodes are moved, not unpacked into a Vec. */
+impl<T> List<T> {
+    pub fn remove_head_n(&mut self, n: usize) -> List<T> {
+        self.split_front(n)
+    }
+}
+
+/* Consume the list into its adjacent pairs, head first: [a, b, c] gives [(a, b), (b, c)].
+ * Every element except the two ends is in two pairs, so those get cloned once. */
+impl<T: Clone> List<T> {
+    pub fn into_pairs(self) -> Vec<(T, T)> {
+        let mut pairs = Vec::new();
+        let mut iter = self.into_iter();
+        if let Some(mut prev) = iter.next() {
+            for elem in iter {
+                pairs.push((prev, elem.clone()));
+                prev = elem;
+            }
+        }
+        pairs
+    }
+}
+
+/* Swap the values of the first and last elements. IterMut hands out non-overlapping
+ * &mut references, so we can hold the first one while running to the end for the last.
+ * Lists with fewer than two elements have no last element after the first, so
+ * nothing happens. */
+impl<T> List<T> {
+    pub fn swap_ends(&mut self) {
+        let mut iter = self.iter_mut();
+        if let (Some(first), Some(last)) = (iter.next(), iter.last()) {
+            std::mem::swap(first, last);
+        }
+    }
+}
+
+/* f64 isn't Ord because of NaN, so ascending sort uses f64::total_cmp, which gives every
+ * float a fixed place: -NaN < -inf < ... < -0.0 < 0.0 < ... < inf < NaN. So the usual
+ * (positive) NaN, like f64::NAN, ends up at the tail. We sort a copy of the values and
+ * write them back into the existing nodes. */
+impl List<f64> {
+    pub fn sort_floats(&mut self) {
+        let mut sorted: Vec<f64> = self.iter().copied().collect();
+        sorted.sort_by(f64::total_cmp);
+        for (slot, value) in self.iter_mut().zip(sorted) {
+            *slot = value;
+        }
+    }
+}
+
+/* Consume the list into sublists of size nodes each (the last one may be shorter),
+ * in order. The nodes are moved, never copied. Panics on a size of 0, like slice::chunks. */
+impl<T> List<T> {
+    pub fn chunks(mut self, size: usize) -> Vec<List<T>> {
+        if size == 0 {
+            panic!("chunk size must be non-zero");
+        }
+        let mut chunks = Vec::new();
+        while !self.is_empty() {
+            chunks.push(self.split_front(size));
+        }
+        chunks
+    }
+}
+
+/* Rotate the list left by n: the first n elements move to the back, in order.
+ * n wraps around the length, so rotating by a multiple of len() changes nothing.
+ * There's no tail pointer to jump to, so we walk once to count, once to cut off the
+ * front and once to find the end again; the nodes are relinked, never moved. */
+impl<T> List<T> {
+    pub fn rotate_left(&mut self, n: usize) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let n = n % len;
+        if n > 0 {
+            let mut front = self.split_front(n);
+            self.append(&mut front);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{List, ListError, OrderedList};
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashMap;
+    use std::hash::{Hash, Hasher};
+    #[test]
+    fn basics() {
+        let mut list = List::new();
+
+        // Check empty list behaves right
+        assert_eq!(list.pop(), None);
+
+        // Populate list
+        list.push(1);
+        list.push(2);
+        list.push(3);
+
+        // Check normal removal
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(2));
+
+        // Push some more just to make sure nothing's corrupted
+        list.push(4);
+        list.push(5);
+
+        // Check normal removal
+        assert_eq!(list.pop(), Some(5));
+        assert_eq!(list.pop(), Some(4));
+
+        // Check exhaustion
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+    }
+    #[test]
+    fn peek() {
+        let mut list = List::new();
+        assert_eq!(list.peek(), None);
+        assert_eq!(list.peek_mut(), None);
+        list.push(1); list.push(2); list.push(3);
+
+        assert_eq!(list.peek(), Some(&3)); // this is interesting...didn't think you could have a reference to a literal. */
+        assert_eq!(list.peek_mut(), Some(&mut 3));
+
+        /* This is a test to see if we can actually change the calue of the first element.*/
+        if let Some(value) = list.peek_mut() {
+            *value = 42
+        }
+
+        assert_eq!(list.peek(), Some(&42));
+        assert_eq!(list.pop(), Some(42));
+    }
+    #[test]
+    fn into_iter() {
+        let mut list = List::new();
+        list.push(1); list.push(2); list.push(3);
+
+        let mut iter = list.into_iter(); // note: value moved here
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), None);
+    }
+    #[test]
+    fn flatten() {
+        let mut a = List::new();
+        a.push(2); a.push(1);
+        let mut b = List::new();
+        b.push(3);
+        let c = List::new();
+        let mut d = List::new();
+        d.push(4);
+
+        let mut lists = List::new();
+        lists.push(d); lists.push(c); lists.push(b); lists.push(a);
+
+        let flat = lists.flatten();
+        assert_eq!(flat.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+    }
+    #[test]
+    fn try_get() {
+        let mut list = List::new();
+        list.push(1); list.push(2); list.push(3);
+
+        assert_eq!(list.try_get(0), Ok(&3));
+        assert_eq!(list.try_get(2), Ok(&1));
+        assert_eq!(list.try_get(5), Err(ListError::IndexOutOfBounds { index: 5, len: 3 }));
+        assert_eq!(
+            list.try_get(3).unwrap_err().to_string(),
+            "index 3 is out of bounds for a list of length 3"
+        );
+    }
+    #[test]
+    fn hash_map_key() {
+        let mut key = List::new();
+        key.push(1); key.push(2);
+        let mut map = HashMap::new();
+        map.insert(key, "found");
+
+        let mut lookup = List::new();
+        lookup.push(1); lookup.push(2);
+        assert_eq!(map.get(&lookup), Some(&"found"));
+
+        lookup.push(3);
+        assert_eq!(map.get(&lookup), None);
+    }
+    #[test]
+    fn rposition() {
+        let mut list = List::new();
+        list.push(3); list.push(1); list.push(2); list.push(1);
+
+        assert_eq!(list.position(|&x| x == 1), Some(0));
+        assert_eq!(list.rposition(|&x| x == 1), Some(2));
+        assert_eq!(list.rposition(|&x| x == 3), Some(3));
+        assert_eq!(list.rposition(|&x| x == 7), None);
+    }
+    #[test]
+    fn peek_nth() {
+        let mut list = List::new();
+        list.push(1); list.push(2); list.push(3);
+
+        assert_eq!(list.peek_nth(0), list.peek());
+        assert_eq!(list.peek_nth(2), Some(&1));
+        assert_eq!(list.peek_nth(3), None);
+    }
+    #[test]
+    fn try_push() {
+        let mut list = List::new();
+        assert_eq!(list.try_push(1), Ok(()));
+        assert_eq!(list.try_push(2), Ok(()));
+        assert_eq!(list.pop(), Some(2));
+
+        // pretend the list is already at the limit: nothing gets pushed
+        assert_eq!(list.try_push_at_len(usize::MAX, 3), Err("list length would overflow usize"));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(list.try_push_at_len(usize::MAX - 1, 3), Ok(()));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &1]);
+    }
+    #[test]
+    fn iter_mut() {
+        let mut list = List::new();
+        list.push(1); list.push(2); list.push(3);
+
+        let mut iter = list.iter_mut();
+        assert_eq!(iter.next(), Some(&mut 3));
+        assert_eq!(iter.next(), Some(&mut 2));
+        assert_eq!(iter.next(), Some(&mut 1));
+        assert_eq!(iter.next(), None);
+    }
+    #[test]
+    fn for_each_mut() {
+        let mut list = List::new();
+        list.push(3); list.push(2); list.push(1);
+
+        list.for_each_mut(|x| *x *= 2);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &4, &6]);
+    }
+    #[test]
+    fn ensure_head() {
+        let mut list = List::new();
+        assert_eq!(list.ensure_head(1), &mut 1);
+        *list.ensure_head(2) += 10;
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.peek(), Some(&11));
+
+        list.push(5);
+        assert_eq!(list.ensure_head(7), &mut 5);
+        assert_eq!(list.len(), 2);
+    }
+    #[test]
+    fn split_halves() {
+        let mut list = List::new();
+        list.push(4); list.push(3); list.push(2); list.push(1);
+        let (front, back) = list.split_halves();
+        assert_eq!(front.iter().collect::<Vec<_>>(), vec![&1, &2]);
+        assert_eq!(back.iter().collect::<Vec<_>>(), vec![&3, &4]);
+
+        let mut list = List::new();
+        list.push(3); list.push(2); list.push(1);
+        let (front, back) = list.split_halves();
+        assert_eq!(front.iter().collect::<Vec<_>>(), vec![&1, &2]);
+        assert_eq!(back.iter().collect::<Vec<_>>(), vec![&3]);
+
+        let mut list = List::new();
+        list.push(1);
+        let (front, back) = list.split_halves();
+        assert_eq!(front.iter().collect::<Vec<_>>(), vec![&1]);
+        assert!(back.is_empty());
+
+        let (front, back) = List::<i32>::new().split_halves();
+        assert!(front.is_empty());
+        assert!(back.is_empty());
+    }
+    #[test]
+    fn boxed_iter() {
+        struct Holder<'a> {
+            iter: Box<dyn Iterator<Item = &'a i32> + 'a>,
+        }
+
+        let mut list = List::new();
+        list.push(1); list.push(2); list.push(3);
+
+        let holder = Holder { iter: list.boxed_iter() };
+        assert_eq!(holder.iter.collect::<Vec<_>>(), vec![&3, &2, &1]);
+    }
+    #[test]
+    fn max_min_by_key() {
+        let mut words = List::new();
+        words.push(String::from("fig"));
+        words.push(String::from("banana"));
+        words.push(String::from("cherry"));
+        words.push(String::from("kiwi"));
+        // "cherry" and "banana" tie, and "cherry" is closer to the head
+        assert_eq!(words.max_by_key(|w| w.len()), Some(&String::from("cherry")));
+        assert_eq!(words.min_by_key(|w| w.len()), Some(&String::from("fig")));
+
+        let mut nums = List::new();
+        nums.push(-1); nums.push(5); nums.push(1); nums.push(-7);
+        assert_eq!(nums.min_by_key(|x: &i32| x.abs()), Some(&1));
+        assert_eq!(nums.max_by_key(|x: &i32| x.abs()), Some(&-7));
+
+        let empty: List<i32> = List::new();
+        assert_eq!(empty.max_by_key(|&x| x), None);
+        assert_eq!(empty.min_by_key(|&x| x), None);
+    }
+    #[test]
+    fn raw_nodes() {
+        use super::raw;
+
+        let empty: List<i32> = List::new();
+        assert!(raw::head_node(&empty).is_none());
+
+        let mut list = List::new();
+        list.push(1); list.push(2); list.push(3);
+
+        let mut seen = Vec::new();
+        let mut cur = raw::head_node(&list);
+        while let Some(node) = cur {
+            seen.push(*node.elem());
+            cur = node.next();
+        }
+        assert_eq!(seen, vec![3, 2, 1]);
+    }
+    #[test]
+    fn for_each_window() {
+        let mut list = List::new();
+        list.push(4); list.push(3); list.push(2); list.push(1);
+
+        let mut windows = Vec::new();
+        list.for_each_window(2, |w| windows.push(vec![*w[0], *w[1]]));
+        assert_eq!(windows, vec![vec![1, 2], vec![2, 3], vec![3, 4]]);
+
+        let mut calls = 0;
+        list.for_each_window(5, |_| calls += 1);
+        assert_eq!(calls, 0);
+    }
+    #[test]
+    fn pop_n() {
+        let mut list = List::new();
+        list.push(5); list.push(4); list.push(3); list.push(2); list.push(1);
+
+        assert_eq!(list.pop_n(2), vec![1, 2]);
+        assert_eq!(list.pop_n(0), Vec::<i32>::new());
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.pop_n(3), vec![3, 4, 5]);
+        assert!(list.is_empty());
+
+        list.push(7);
+        assert_eq!(list.pop_n(10), vec![7]);
+        assert!(list.is_empty());
+    }
+    #[test]
+    fn freeze() {
+        let mut list = List::new();
+        list.push(1); list.push(2); list.push(3);
+        let mut hasher = DefaultHasher::new();
+        list.hash(&mut hasher);
+        let fresh = hasher.finish();
+
+        let frozen = list.freeze();
+        assert_eq!(frozen.cached_hash(), fresh);
+        assert_eq!(frozen.peek(), Some(&3));
+
+        let mut map = HashMap::new();
+        map.insert(frozen, "found");
+
+        let mut lookup = List::new();
+        lookup.push(1); lookup.push(2); lookup.push(3);
+        let lookup = lookup.freeze();
+        assert_eq!(map.get(&lookup), Some(&"found"));
+
+        let mut other = lookup.into_inner();
+        other.push(4);
+        assert_eq!(map.get(&other.freeze()), None);
+    }
+    #[test]
+    fn iter_mut_rev() {
+        let mut list = List::new();
+        list.push(3); list.push(2); list.push(1);
+
+        let mut iter = list.iter_mut();
+        *iter.next_back().unwrap() = 30;
+        *iter.next().unwrap() = 10;
+        assert_eq!(iter.next_back(), Some(&mut 2));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&10, &2, &30]);
+
+        for x in list.iter_mut().rev().take(1) {
+            *x += 1;
+        }
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&10, &2, &31]);
+    }
+    #[test]
+    fn compacted_copy() {
+        let mut list = List::new();
+        list.push(3); list.push(2); list.push(1);
+
+        let snapshot = list.compacted_copy();
+        assert_eq!(snapshot, vec![1, 2, 3]);
+
+        list.push(0);
+        list.rebuild_from_vec(snapshot);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+
+        list.rebuild_from_vec(Vec::new());
+        assert!(list.is_empty());
+    }
+    #[test]
+    fn into_boxed_iter() {
+        let iter = {
+            let mut list = List::new();
+            list.push(1); list.push(2); list.push(3);
+            list.into_boxed_iter()
+        };
+        assert_eq!(iter.collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+    #[cfg(debug_assertions)]
+    #[test]
+    fn verify() {
+        let mut list = List::new();
+        list.verify();
+        list.push(1); list.push(2); list.push(3); list.push(4); list.push(5);
+        list.verify();
+        list.pop();
+        list.verify();
+        list.pop_n(2);
+        list.verify();
+        list.rebuild_from_vec(vec![1, 2, 3]);
+        list.verify();
+
+        let (front, back) = list.split_halves();
+        front.verify();
+        back.verify();
+
+        let mut lists = List::new();
+        lists.push(back); lists.push(front);
+        let flat = lists.flatten();
+        flat.verify();
+        assert_eq!(flat.len(), 3);
+    }
+    #[test]
+    fn merge_k_sorted() {
+        let mut a = List::new();
+        a.push(9); a.push(4); a.push(1);
+        let mut b = List::new();
+        b.push(5); b.push(2);
+        let mut c = List::new();
+        c.push(10); c.push(8); c.push(7); c.push(4); c.push(3);
+
+        let merged = List::merge_k_sorted(vec![a, List::new(), b, c]);
+        assert_eq!(
+            merged.iter().collect::<Vec<_>>(),
+            vec![&1, &2, &3, &4, &4, &5, &7, &8, &9, &10]
+        );
+
+        assert!(List::<i32>::merge_k_sorted(Vec::new()).is_empty());
+    }
+    #[test]
+    fn keep_first_matching() {
+        let mut list = List::new();
+        list.push(6); list.push(5); list.push(4); list.push(3); list.push(2); list.push(1);
+
+        list.keep_first_matching(2, |x| x % 2 == 0);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &4]);
+
+        list.keep_first_matching(5, |x| x % 2 == 0);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &4]);
+
+        list.keep_first_matching(0, |_| true);
+        assert!(list.is_empty());
+    }
+    #[test]
+    fn render() {
+        let mut list = List::new();
+        assert_eq!(list.render(" -> "), "None");
+
+        list.push(1); list.push(2); list.push(3);
+        assert_eq!(list.render(" -> "), "3 -> 2 -> 1 -> None");
+        assert_eq!(list.render("=>"), "3=>2=>1=>None");
+    }
+    #[test]
+    fn move_to_front() {
+        let mut list = List::new();
+        list.push(4); list.push(3); list.push(2); list.push(1);
+
+        assert!(list.move_to_front(&3));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &1, &2, &4]);
+
+        assert!(list.move_to_front(&3));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &1, &2, &4]);
+
+        assert!(list.move_to_front(&4));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&4, &3, &1, &2]);
+
+        assert!(!list.move_to_front(&7));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&4, &3, &1, &2]);
+    }
+    #[test]
+    fn bytes() {
+        let list = List::from_bytes(b"abc");
+        assert_eq!(list.peek(), Some(&b'a'));
+
+        for data in [&b""[..], &b"x"[..], &b"hello"[..], &[0, 255, 7][..]].iter() {
+            assert_eq!(List::from_bytes(data).into_bytes(), data.to_vec());
+        }
+    }
+    #[test]
+    fn range() {
+        let mut list = List::new();
+        list.push(5); list.push(4); list.push(3); list.push(2); list.push(1);
+
+        assert_eq!(list.range(1, 4), vec![&2, &3, &4]);
+        assert_eq!(list.range(0, 5), vec![&1, &2, &3, &4, &5]);
+        assert_eq!(list.range(2, 2), Vec::<&i32>::new());
+    }
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn range_out_of_bounds() {
+        let mut list = List::new();
+        list.push(1); list.push(2);
+        list.range(1, 3);
+    }
+    #[test]
+    #[should_panic(expected = "greater than range end")]
+    fn range_backwards() {
+        let mut list = List::new();
+        list.push(1); list.push(2);
+        list.range(2, 1);
+    }
+    #[test]
+    fn split_by_index_parity() {
+        let mut list = List::new();
+        list.push('e'); list.push('d'); list.push('c'); list.push('b'); list.push('a');
+
+        let (evens, odds) = list.split_by_index_parity();
+        assert_eq!(evens.iter().collect::<String>(), "ace");
+        assert_eq!(odds.iter().collect::<String>(), "bd");
+
+        let (evens, odds) = List::<char>::new().split_by_index_parity();
+        assert!(evens.is_empty());
+        assert!(odds.is_empty());
+    }
+    #[test]
+    fn remove_all() {
+        let mut list = List::new();
+        list.push(2); list.push(3); list.push(2); list.push(2); list.push(1);
+
+        assert_eq!(list.remove_all(&2), 3);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &3]);
+        assert_eq!(list.remove_all(&7), 0);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &3]);
+    }
+    #[test]
+    fn peek_or() {
+        let fallback = 0;
+        let mut list = List::new();
+        assert_eq!(list.peek_or(&fallback), &0);
+
+        list.push(5);
+        let head = list.peek_or(&fallback);
+        assert_eq!(head, &5);
+    }
+    #[test]
+    fn push_all() {
+        let mut list = List::new();
+        assert_eq!(list.push_all(1..4), 3);
+        assert_eq!(list.push_all(vec![10, 20]), 2);
+        assert_eq!(list.push_all(Vec::new()), 0);
+
+        assert_eq!(list.pop(), Some(20));
+        assert_eq!(list.pop(), Some(10));
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+    }
+    #[test]
+    fn with_head_mut() {
+        let mut list = List::new();
+        assert_eq!(list.with_head_mut(|x: &mut i32| *x + 1), None);
+
+        list.push(1); list.push(2);
+        let old = list.with_head_mut(|x| {
+            let old = *x;
+            *x *= 10;
+            old
+        });
+        assert_eq!(old, Some(2));
+        assert_eq!(list.peek(), Some(&20));
+    }
+    #[test]
+    fn prefix_scan() {
+        let mut list = List::new();
+        assert_eq!(list.prefix_scan(0, |acc, x| acc + x), Vec::<i32>::new());
+
+        list.push(3); list.push(2); list.push(1);
+        assert_eq!(list.prefix_scan(0, |acc, x| acc + x), vec![1, 3, 6]);
+    }
+    #[test]
+    fn chars() {
+        let list = List::from_chars("hello");
+        assert_eq!(list.peek(), Some(&'h'));
+        assert_eq!(list.into_string(), "hello");
+
+        let empty = List::from_chars("");
+        assert!(empty.is_empty());
+        assert_eq!(empty.into_string(), "");
+    }
+    #[cfg(debug_assertions)]
+    #[test]
+    fn cycle_entry() {
+        let mut list = List::new();
+        list.push(4); list.push(3); list.push(2); list.push(1); list.push(0);
+        assert_eq!(list.cycle_entry(), None);
+        assert_eq!(List::<i32>::new().cycle_entry(), None);
+    }
+    #[cfg(debug_assertions)]
+    #[test]
+    fn floyd_entry() {
+        // node i links to table[i]; indices can form cycles without any unsafe
+        let entry = |table: &[Option<usize>]| super::floyd_entry(Some(0), |i| table[i], |a, b| a == b);
+        assert_eq!(entry(&[Some(1), Some(2), Some(0)]), Some(0));
+        assert_eq!(entry(&[Some(1), Some(2), Some(3), Some(4), Some(2)]), Some(2));
+        assert_eq!(entry(&[Some(1), Some(2), Some(2)]), Some(2));
+        assert_eq!(entry(&[Some(0)]), Some(0));
+        assert_eq!(entry(&[Some(1), Some(2), Some(3), None]), None);
+        assert_eq!(entry(&[None]), None);
+        assert_eq!(super::floyd_entry(None, |i: usize| Some(i), |a, b| a == b), None);
+    }
+    #[test]
+    fn partition_point() {
+        let mut list = List::new();
+        list.push(5); list.push(4); list.push(3); list.push(2); list.push(1);
+
+        assert_eq!(list.partition_point(|&x| x < 3), 2);
+        assert_eq!(list.partition_point(|_| true), 5);
+        assert_eq!(list.partition_point(|_| false), 0);
+    }
+    #[test]
+    fn dedup_by_key() {
+        let mut list = List::new();
+        for word in ["fig", "kiwi", "lime", "pear", "plum", "date", "apple"].iter().rev() {
+            list.push(word.to_string());
+        }
+
+        list.dedup_by_key(|s| s.len());
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec!["fig", "kiwi", "apple"]);
+    }
+    #[test]
+    fn from_iter_checked() {
+        let list = List::from_iter_checked(1..4, 3).ok().unwrap();
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+
+        match List::from_iter_checked(vec![1, 2], 5) {
+            Err((list, actual)) => {
+                assert_eq!(actual, 2);
+                assert_eq!(list.len(), 2);
+            }
+            Ok(_) => panic!("expected a length mismatch"),
+        }
+    }
+    #[test]
+    fn from_iter_front() {
+        let list = List::from_iter_front(1..4);
+        assert_eq!(list.peek(), Some(&1));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+
+        assert!(List::<i32>::from_iter_front(Vec::new()).is_empty());
+    }
+    #[test]
+    fn clone() {
+        let mut list = List::new();
+        list.push(3); list.push(2); list.push(1);
+        let copy = list.clone();
+        assert_eq!(copy.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+
+        // the source is shorter, so the extra node goes away
+        let mut short = List::new();
+        short.push(20); short.push(10);
+        let mut target = list.clone();
+        let first_node: *const i32 = target.peek().unwrap();
+        target.clone_from(&short);
+        assert_eq!(target.iter().collect::<Vec<_>>(), vec![&10, &20]);
+        assert_eq!(target.peek().unwrap() as *const i32, first_node);
+
+        // the source is longer, so new nodes get added on the end
+        let mut target = short.clone();
+        target.clone_from(&list);
+        assert_eq!(target.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+
+        target.clone_from(&List::new());
+        assert!(target.is_empty());
+    }
+    #[test]
+    fn iter_mut_indexed() {
+        let mut list = List::new();
+        list.push(7); list.push(8); list.push(9);
+
+        for (i, elem) in list.iter_mut_indexed() {
+            *elem = i;
+        }
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&0, &1, &2]);
+    }
+    #[test]
+    fn take_until() {
+        let mut list = List::new();
+        list.push(3); list.push(0); list.push(2); list.push(1);
+
+        let prefix = list.take_until(&0);
+        assert_eq!(prefix.iter().collect::<Vec<_>>(), vec![&1, &2]);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&0, &3]);
+
+        let prefix = list.take_until(&0);
+        assert!(prefix.is_empty());
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&0, &3]);
+
+        let prefix = list.take_until(&9);
+        assert_eq!(prefix.iter().collect::<Vec<_>>(), vec![&0, &3]);
+        assert!(list.is_empty());
+    }
+    #[test]
+    fn swap_nodes() {
+        let mut list = List::new();
+        list.push(5); list.push(4); list.push(3); list.push(2); list.push(1);
+        let third: *const i32 = list.peek_nth(2).unwrap();
+
+        list.swap_nodes(2, 3);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &4, &3, &5]);
+        // the node moved, not just its value
+        assert_eq!(list.peek_nth(3).unwrap() as *const i32, third);
+
+        list.swap_nodes(3, 0);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &2, &4, &1, &5]);
+
+        list.swap_nodes(0, 4);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&5, &2, &4, &1, &3]);
+
+        list.swap_nodes(1, 1);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&5, &2, &4, &1, &3]);
+    }
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn swap_nodes_out_of_bounds() {
+        let mut list = List::new();
+        list.push(1); list.push(2);
+        list.swap_nodes(0, 2);
+    }
+    #[test]
+    fn drain_into() {
+        let mut list = List::new();
+        list.push(3); list.push(2); list.push(1);
+        let mut out = vec![10, 20];
+
+        list.drain_into(&mut out);
+        assert_eq!(out, vec![10, 20, 1, 2, 3]);
+        assert!(list.is_empty());
+    }
+    #[test]
+    fn iter_rev_smart() {
+        let mut list = List::new();
+        assert_eq!(list.iter_rev_smart(4).next(), None);
+
+        list.push(3); list.push(2); list.push(1);
+        // shorter than the limit, and exactly at it
+        assert_eq!(list.iter_rev_smart(8).collect::<Vec<_>>(), vec![&3, &2, &1]);
+        assert_eq!(list.iter_rev_smart(3).collect::<Vec<_>>(), vec![&3, &2, &1]);
+        // longer than the limit
+        assert_eq!(list.iter_rev_smart(1).collect::<Vec<_>>(), vec![&3, &2, &1]);
+        assert_eq!(list.iter_rev_smart(0).collect::<Vec<_>>(), vec![&3, &2, &1]);
+        // which buffer gets used
+        assert!(list.rev_fits_inline(3));
+        assert!(!list.rev_fits_inline(2));
+        let full = List::from_vec((0..16).collect());
+        assert!(full.rev_fits_inline(16));
+        let rev: Vec<i32> = full.iter_rev_smart(16).copied().collect();
+        assert_eq!(rev, (0..16).rev().collect::<Vec<_>>());
+        let long = List::from_vec((0..20_000).collect());
+        assert!(!long.rev_fits_inline(16));
+        let rev: Vec<i32> = long.iter_rev_smart(16).copied().collect();
+        assert_eq!(rev, (0..20_000).rev().collect::<Vec<_>>());
+    }
+    #[test]
+    #[should_panic(expected = "stack_limit 100 is more than the 16 elements")]
+    fn iter_rev_smart_limit_too_big() {
+        let list = List::from_vec((0..50).collect());
+        list.iter_rev_smart(100).count();
+    }
+    #[test]
+    fn frequencies_sorted() {
+        let mut list = List::new();
+        list.push(2); list.push(2); list.push(2); list.push(1); list.push(3); list.push(3);
+
+        assert_eq!(list.frequencies().get(&3), Some(&2));
+        assert_eq!(list.frequencies_sorted(), vec![(2, 3), (3, 2), (1, 1)]);
+
+        let mut ties = List::new();
+        ties.push(9); ties.push(4); ties.push(7);
+        assert_eq!(ties.frequencies_sorted(), vec![(4, 1), (7, 1), (9, 1)]);
+    }
+    #[test]
+    fn try_split_off() {
+        let mut list = List::new();
+        list.push(4); list.push(3); list.push(2); list.push(1);
+
+        let tail = list.try_split_off(1).ok().unwrap();
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(tail.iter().collect::<Vec<_>>(), vec![&2, &3, &4]);
+
+        let empty = list.try_split_off(1).ok().unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(list.len(), 1);
+
+        assert!(list.try_split_off(5).err() == Some(1));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1]);
+
+        // split_off clamps instead
+        assert!(list.split_off(5).is_empty());
+        assert_eq!(list.len(), 1);
+    }
+    #[test]
+    fn insert_sorted() {
+        let mut list = List::new();
+        list.insert_sorted(5);
+        list.push(3); list.push(1);
+
+        list.insert_sorted(4);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &3, &4, &5]);
+        list.insert_sorted(0);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&0, &1, &3, &4, &5]);
+        list.insert_sorted(9);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&0, &1, &3, &4, &5, &9]);
+        list.insert_sorted(3);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&0, &1, &3, &3, &4, &5, &9]);
+    }
+    #[test]
+    fn dedup_keep_last() {
+        let mut list = List::new();
+        list.push(2); list.push(3); list.push(1); list.push(2); list.push(1);
+
+        list.dedup_keep_last();
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &3, &2]);
+    }
+    #[test]
+    fn run_lengths() {
+        let mut list = List::new();
+        assert_eq!(list.run_lengths(|a, b| a == b), Vec::<usize>::new());
+
+        list.push(3); list.push(3); list.push(3); list.push(2); list.push(1); list.push(1);
+        assert_eq!(list.run_lengths(|a, b| a == b), vec![2, 1, 3]);
+    }
+    #[test]
+    fn add() {
+        let list = List::from_vec(vec![1, 2]) + List::from_vec(vec![3, 4]);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+
+        let list = List::new() + list + List::new();
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+    }
+    #[test]
+    fn add_assign() {
+        let mut a = List::from_vec(vec![1, 2]);
+        let b = List::from_vec(vec![3]);
+        a += b;
+        a += List::new();
+        assert_eq!(a.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    }
+    #[test]
+    fn partition_dedup() {
+        let mut list = List::from_vec(vec![1, 1, 2, 3, 3]);
+        let dups = list.partition_dedup();
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+        assert_eq!(dups.iter().collect::<Vec<_>>(), vec![&1, &3]);
+
+        let mut list = List::from_vec(vec![4, 4, 4, 5, 4]);
+        let dups = list.partition_dedup();
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&4, &5, &4]);
+        assert_eq!(dups.iter().collect::<Vec<_>>(), vec![&4, &4]);
+    }
+    #[test]
+    fn iter_cloned() {
+        let list = List::from_vec(vec![String::from("a"), String::from("b")]);
+        let owned: Vec<String> = list.iter_cloned().collect();
+        assert_eq!(owned, vec!["a", "b"]);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+    #[test]
+    fn peek_slice() {
+        let list = List::from_vec(vec![1, 2, 3]);
+        assert_eq!(list.peek_slice(2), vec![&1, &2]);
+        assert_eq!(list.peek_slice(3), vec![&1, &2, &3]);
+        assert_eq!(list.peek_slice(10), vec![&1, &2, &3]);
+        assert_eq!(list.len(), 3);
+    }
+    #[test]
+    fn with_split_mut() {
+        let mut list = List::from_vec(vec![1, 2, 3, 4, 5]);
+        list.with_split_mut(
+            2,
+            |front| front.for_each_mut(|x| *x *= 10),
+            |back| {
+                back.pop();
+                back.push(0);
+            },
+        );
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&10, &20, &0, &4, &5]);
+    }
+    #[test]
+    fn trim_matches() {
+        let mut list = List::from_vec(vec![0, 0, 1, 2, 0, 3, 0, 0]);
+        list.trim_matches(|&x| x == 0);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &0, &3]);
+
+        let mut list = List::from_vec(vec![0, 0, 0]);
+        list.trim_matches(|&x| x == 0);
+        assert!(list.is_empty());
+    }
+    #[test]
+    fn ordered_list() {
+        let list = (1..4).collect::<OrderedList<_>>().into_inner();
+        assert_eq!(list.peek(), Some(&1));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    }
+    #[test]
+    fn count_distinct() {
+        assert_eq!(List::from_vec(vec![1, 2, 2, 3, 3, 3]).count_distinct(), 3);
+        assert_eq!(List::<i32>::new().count_distinct(), 0);
+    }
+    #[test]
+    fn positions() {
+        let list = List::from_vec(vec![1, 2, 1, 3, 1]);
+        assert_eq!(list.positions(|&x| x == 1), vec![0, 2, 4]);
+        assert_eq!(list.positions(|&x| x == 7), Vec::<usize>::new());
+    }
+    #[test]
+    fn push_if_absent() {
+        let mut list = List::new();
+        assert!(list.push_if_absent(1));
+        assert!(list.push_if_absent(2));
+        assert!(!list.push_if_absent(1));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &1]);
+    }
+    #[test]
+    fn reverse_range() {
+        let mut list = List::from_vec(vec![1, 2, 3, 4, 5]);
+        list.reverse_range(1, 4);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &4, &3, &2, &5]);
+
+        list.reverse_range(0, 5);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&5, &2, &3, &4, &1]);
+
+        list.reverse_range(2, 2);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&5, &2, &3, &4, &1]);
+    }
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn reverse_range_out_of_bounds() {
+        let mut list = List::from_vec(vec![1, 2, 3]);
+        list.reverse_range(1, 4);
+    }
+    #[test]
+    fn into_sorted_vec() {
+        assert_eq!(List::from_vec(vec![5, 4, 3, 2, 1]).into_sorted_vec(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(List::from_vec(vec![3, 9, 1, 3, 7]).into_sorted_vec(), vec![1, 3, 3, 7, 9]);
+        assert_eq!(List::from_vec(vec![42]).into_sorted_vec(), vec![42]);
+        assert_eq!(List::<i32>::new().into_sorted_vec(), Vec::<i32>::new());
+    }
+    #[test]
+    fn head_equals() {
+        let list = List::from_vec(vec![1, 2]);
+        assert!(list.head_equals(&1));
+        assert!(!list.head_equals(&2));
+        assert!(!List::new().head_equals(&1));
+    }
+    #[test]
+    fn pop_while() {
+        let mut list = List::from_vec(vec![1, 2, 3, 10]);
+        assert_eq!(list.pop_while(|&x| x < 3), vec![1, 2]);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &10]);
+
+        assert_eq!(list.pop_while(|&x| x < 3), Vec::<i32>::new());
+        assert_eq!(list.pop_while(|_| true), vec![3, 10]);
+        assert!(list.is_empty());
+    }
+    #[test]
+    fn take_nth() {
+        let mut list = List::from_vec(vec![1, 2, 3, 4, 5]);
+        assert_eq!(list.take_nth(0), Some(1));
+        assert_eq!(list.take_nth(2), Some(4));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&5]);
+
+        assert_eq!(list.take_nth(3), None);
+        assert!(list.is_empty());
+    }
+    #[test]
+    fn symmetric_difference() {
+        let a = List::from_vec(vec![1, 2, 3, 1]);
+        let b = List::from_vec(vec![2, 3, 4]);
+        let diff = a.symmetric_difference(&b);
+        assert_eq!(diff.iter().collect::<Vec<_>>(), vec![&1, &4]);
+        assert!(a.symmetric_difference(&a).is_empty());
+    }
+    #[test]
+    fn intersection() {
+        let a = List::from_vec(vec![1, 2, 3]);
+        let b = List::from_vec(vec![2, 3, 4]);
+        assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), vec![&2, &3]);
+
+        // self's order wins, and duplicates only show up once
+        let a = List::from_vec(vec![3, 1, 2, 3]);
+        assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), vec![&3, &2]);
+
+        let c = List::from_vec(vec![7, 8]);
+        assert!(a.intersection(&c).is_empty());
+    }
+    #[test]
+    fn union() {
+        let a = List::from_vec(vec![1, 2]);
+        let b = List::from_vec(vec![2, 3]);
+        assert_eq!(a.union(&b).iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+
+        let c = List::from_vec(vec![3, 3, 1]);
+        assert_eq!(c.union(&a).iter().collect::<Vec<_>>(), vec![&3, &1, &2]);
+    }
+    #[test]
+    fn zip() {
+        let nums = List::from_vec(vec![1, 2, 3]);
+        let letters = List::from_vec(vec!['a', 'b']);
+        assert_eq!(nums.zip(&letters).collect::<Vec<_>>(), vec![(&1, &'a'), (&2, &'b')]);
+    }
+    #[test]
+    fn single() {
+        assert_eq!(List::<i32>::new().single(), Ok(None));
+        assert_eq!(List::from_vec(vec![7]).single(), Ok(Some(&7)));
+        assert_eq!(List::from_vec(vec![7, 8, 9]).single(), Err(3));
+    }
+    #[test]
+    fn fold_mut() {
+        let mut list = List::from_vec(vec![1, 2, 3]);
+        let sum = list.fold_mut(0, |sum, x| {
+            let before = *x;
+            *x *= 2;
+            sum + before
+        });
+        assert_eq!(sum, 6);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &4, &6]);
+    }
+    #[test]
+    fn remove_head_n() {
+        let mut list = List::from_vec(vec![1, 2, 3, 4]);
+        let front = list.remove_head_n(1);
+        assert_eq!(front.iter().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &3, &4]);
+
+        let front = list.remove_head_n(3);
+        assert_eq!(front.iter().collect::<Vec<_>>(), vec![&2, &3, &4]);
+        assert!(list.is_empty());
+
+        let mut list = List::from_vec(vec![5, 6]);
+        let front = list.remove_head_n(10);
+        assert_eq!(front.iter().collect::<Vec<_>>(), vec![&5, &6]);
+        assert!(list.is_empty());
+    }
+    #[test]
+    fn into_pairs() {
+        assert_eq!(List::from_vec(vec![1, 2, 3]).into_pairs(), vec![(1, 2), (2, 3)]);
+        assert_eq!(List::from_vec(vec![1]).into_pairs(), Vec::new());
+        assert_eq!(List::<i32>::new().into_pairs(), Vec::new());
+    }
+    #[test]
+    fn swap_ends() {
+        let mut list = List::from_vec(vec![1, 2, 3, 4]);
+        list.swap_ends();
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&4, &2, &3, &1]);
+
+        let mut list = List::from_vec(vec![1]);
+        list.swap_ends();
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1]);
+
+        let mut list = List::<i32>::new();
+        list.swap_ends();
+        assert!(list.is_empty());
+    }
+    #[test]
+    fn sort_floats() {
+        let mut list = List::from_vec(vec![3.0, f64::NAN, 1.0, 2.0]);
+        list.sort_floats();
+        let sorted: Vec<f64> = list.iter().copied().collect();
+        assert_eq!(&sorted[..3], &[1.0, 2.0, 3.0]);
+        assert!(sorted[3].is_nan());
+
+        let mut list = List::from_vec(vec![0.5, -f64::NAN, -1.5]);
+        list.sort_floats();
+        assert!(list.peek().unwrap().is_nan());
+        assert_eq!(list.peek_nth(1), Some(&-1.5));
+    }
+    #[test]
+    fn chunks() {
+        let chunks = List::from_vec(vec![1, 2, 3, 4, 5]).chunks(2);
+        let chunks: Vec<Vec<i32>> = chunks.into_iter().map(|c| c.into_iter().collect()).collect();
+        assert_eq!(chunks, vec![vec![1, 2], vec![3, 4], vec![5]]);
+
+        assert!(List::<i32>::new().chunks(3).is_empty());
+    }
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn chunks_zero() {
+        List::from_vec(vec![1]).chunks(0);
+    }
+    #[test]
+    fn rotate_left() {
+        let mut list = List::from_vec(vec![1, 2, 3, 4]);
+        list.rotate_left(1);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &3, &4, &1]);
+        let mut list = List::from_vec(vec![1, 2, 3, 4]);
+        list.rotate_left(4);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+        let mut list = List::from_vec(vec![1, 2, 3, 4]);
+        list.rotate_left(6);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &4, &1, &2]);
+        let mut empty: List<i32> = List::new();
+        empty.rotate_left(3);
+        assert!(empty.is_empty());
+    }
 }
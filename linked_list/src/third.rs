@@ -0,0 +1,546 @@
+/* A persistent, immutable singly-linked list.
+ * Lists can share their tails, so a node might be owned by more than one list.
+ * That means Box doesn't work anymore; we need a reference-counted pointer, Rc. */
+use std::collections::HashSet;
+use std::rc::Rc;
+
+pub struct List<T> {
+    head: Link<T>,
+}
+
+type Link<T> = Option<Rc<Node<T>>>;
+
+struct Node<T> {
+    elem: T,
+    next: Link<T>,
+}
+
+impl<T> List<T> {
+    pub fn new() -> Self {
+        List { head: None }
+    }
+
+    /* returns a new list with elem in front of this one. The old list is untouched,
+     * and the two lists share every node after the new head. Cloning an Rc just
+     * bumps the reference count. */
+    pub fn prepend(&self, elem: T) -> List<T> {
+        List {
+            head: Some(Rc::new(Node {
+                elem,
+                next: self.head.clone(),
+            })),
+        }
+    }
+
+    /* the list without its first element. On an empty list this is just another empty list. */
+    pub fn tail(&self) -> List<T> {
+        List {
+            head: self.head.as_ref().and_then(|node| node.next.clone()),
+        }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.elem)
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
+}
+
+/* Cloning a persistent list is cheap: the clone shares every node with the original,
+ * we only bump the head's reference count. No T: Clone needed. */
+impl<T> Clone for List<T> {
+    fn clone(&self) -> Self {
+        List { head: self.head.clone() }
+    }
+}
+
+/* add the Iter - &T. Same as second.rs, only the pointer type changed. */
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<T> List<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head.as_deref() }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.elem
+        })
+    }
+}
+
+/* so that `for x in &list` works. */
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/* We still want an iterative drop, but we can only tear down a node if we're the
+ * last list holding it. Rc::try_unwrap gives us the node back in exactly that case;
+ * otherwise someone else still needs the rest of the list, so we stop. */
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        let mut head = self.head.take();
+        while let Some(node) = head {
+            if let Ok(mut node) = Rc::try_unwrap(node) {
+                head = node.next.take();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+/* Count the elements matching pred. This only borrows through iter(), so no
+ * reference counts change. */
+impl<T> List<T> {
+    pub fn count_where<F: FnMut(&T) -> bool>(&self, mut pred: F) -> usize {
+        self.iter().filter(|elem| pred(elem)).count()
+    }
+}
+
+/* Build a new list by applying f to every element. prepend puts things at the front,
+ * so to keep the order we collect the mapped values first and prepend them back to front. */
+impl<T> List<T> {
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> List<U> {
+        List::from_ordered_vec(self.iter().map(f).collect())
+    }
+
+    /* elems[0] ends up as the head. */
+    fn from_ordered_vec(elems: Vec<T>) -> List<T> {
+        elems.into_iter().rev().fold(List::new(), |list, elem| list.prepend(elem))
+    }
+}
+
+/* Build a new list with only the elements passing pred, in the same order.
+ * The nodes are shared with other lists, so we can't move the values out; they get cloned. */
+impl<T: Clone> List<T> {
+    pub fn filter<F: FnMut(&T) -> bool>(&self, mut pred: F) -> List<T> {
+        List::from_ordered_vec(self.iter().filter(|elem| pred(elem)).cloned().collect())
+    }
+}
+
+/* Fold over borrowed elements, head to tail. */
+impl<T> List<T> {
+    pub fn fold<B, F: FnMut(B, &T) -> B>(&self, init: B, f: F) -> B {
+        self.iter().fold(init, f)
+    }
+}
+
+/* Mutable access to the head, but only if this list is the sole owner of the head node.
+ * Rc::get_mut returns None as soon as anyone else holds the node (another list made
+ * with prepend on top of us, or a clone of our tail), and we pass that None along
+ * instead of cloning. Use with care: writing through this changes a value that
+ * other code may have already looked at, which is not very "persistent". */
+impl<T> List<T> {
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head
+            .as_mut()
+            .and_then(|node| Rc::get_mut(node))
+            .map(|node| &mut node.elem)
+    }
+}
+
+/* prepend every element of the slice, one after the other, starting at elems[0].
+ * Just like calling prepend in a loop, this means the *last* slice element ends up
+ * as the new head: prepend_many(&[1, 2, 3]) onto [4] gives [3, 2, 1, 4]. */
+impl<T: Clone> List<T> {
+    pub fn prepend_many(&self, elems: &[T]) -> List<T> {
+        elems.iter().fold(self.clone(), |list, elem| list.prepend(elem.clone()))
+    }
+}
+
+/* Like tail(), but tells you when there was no tail to take: None on an empty list. */
+impl<T> List<T> {
+    pub fn try_tail(&self) -> Option<List<T>> {
+        self.head.as_ref().map(|node| List { head: node.next.clone() })
+    }
+}
+
+/* How many nodes at the end of the two lists are literally the same nodes (same Rc
+ * allocation, not just equal values). That's the structure that tail/prepend share.
+ * Once two lists reach a common node, everything after it is common too, so we line
+ * the lists up by skipping the extra nodes of the longer one, then walk both in step
+ * until they meet. */
+impl<T> List<T> {
+    pub fn shared_suffix_len(&self, other: &List<T>) -> usize {
+        let len_a = self.iter().count();
+        let len_b = other.iter().count();
+        let mut a = Self::skip_links(self.head.as_ref(), len_a.saturating_sub(len_b));
+        let mut b = Self::skip_links(other.head.as_ref(), len_b.saturating_sub(len_a));
+        let mut remaining = len_a.min(len_b);
+        while let (Some(node_a), Some(node_b)) = (a, b) {
+            if Rc::ptr_eq(node_a, node_b) {
+                return remaining;
+            }
+            a = node_a.next.as_ref();
+            b = node_b.next.as_ref();
+            remaining -= 1;
+        }
+        0
+    }
+
+    fn skip_links(mut link: Option<&Rc<Node<T>>>, n: usize) -> Option<&Rc<Node<T>>> {
+        for _ in 0..n {
+            link = link.and_then(|node| node.next.as_ref());
+        }
+        link
+    }
+}
+
+/* The reference count of the head node, 0 for an empty list. Handy for watching
+ * structural sharing happen: every clone, prepend or tail pointing at the head bumps it. */
+impl<T> List<T> {
+    pub fn strong_count_at_head(&self) -> usize {
+        self.head.as_ref().map_or(0, Rc::strong_count)
+    }
+}
+
+/* head() and tail() in one go. The tail shares its nodes with self, nothing is cloned
+ * but an Rc. Calling uncons on the tail again walks down the list. */
+impl<T> List<T> {
+    pub fn uncons(&self) -> Option<(&T, List<T>)> {
+        self.head
+            .as_ref()
+            .map(|node| (&node.elem, List { head: node.next.clone() }))
+    }
+}
+
+/* Build a persistent list in the iterator's order (its first item is the head).
+ * Buffers everything into a Vec and prepends it back to front, like map. */
+impl<T> List<T> {
+    pub fn from_iter_ordered<I: IntoIterator<Item = T>>(iter: I) -> List<T> {
+        List::from_ordered_vec(iter.into_iter().collect())
+    }
+}
+
+/* Reverse a persistent list, reusing as many nodes as possible.
+ * While a node belongs only to us (Rc::get_mut works), we flip its next pointer in place,
+ * just like reversing an owned list. As soon as we reach a node somebody else also holds,
+ * we can't touch it, and since that node keeps everything after it alive, none of the
+ * rest is ours either. So from there on the elements get cloned into fresh nodes.
+ * A list that shares nothing is reversed without a single clone or allocation. */
+impl<T: Clone> List<T> {
+    pub fn reverse_owned(mut self) -> List<T> {
+        let mut reversed: Link<T> = None;
+        let mut cur_link = self.head.take();
+        while let Some(mut rc) = cur_link {
+            match Rc::get_mut(&mut rc) {
+                Some(node) => {
+                    cur_link = node.next.take();
+                    node.next = reversed;
+                    reversed = Some(rc);
+                }
+                None => {
+                    let shared = List { head: Some(rc) };
+                    for elem in shared.iter() {
+                        reversed = Some(Rc::new(Node { elem: elem.clone(), next: reversed }));
+                    }
+                    break;
+                }
+            }
+        }
+        List { head: reversed }
+    }
+}
+
+/* Put everything from iter in front of this list, keeping the iterator's order:
+ * its first item becomes the new head, and the old list follows the last one.
+ * The items are buffered and prepended back to front. The old list's nodes are shared,
+ * and the items are moved in, so T doesn't need to be Clone. */
+impl<T> List<T> {
+    pub fn prepend_iter<I: IntoIterator<Item = T>>(&self, iter: I) -> List<T> {
+        let items: Vec<T> = iter.into_iter().collect();
+        items.into_iter().rev().fold(self.clone(), |list, elem| list.prepend(elem))
+    }
+}
+
+/* How many distinct nodes all these lists hold between them. Shared tails are only
+ * counted once: nodes are told apart by their address, and once we hit a node we've
+ * already seen, the rest of that list has been counted too, so we can stop early. */
+impl<T> List<T> {
+    pub fn count_unique_nodes(lists: &[&List<T>]) -> usize {
+        let mut seen: HashSet<*const Node<T>> = HashSet::new();
+        for list in lists {
+            let mut link = list.head.as_ref();
+            while let Some(node) = link {
+                if !seen.insert(Rc::as_ptr(node)) {
+                    break;
+                }
+                link = node.next.as_ref();
+            }
+        }
+        seen.len()
+    }
+}
+
+/* Indexing that tells you why it failed: Err carries the list's length. The length
+ * is only counted when the index turns out to be out of range. */
+impl<T> List<T> {
+    pub fn try_get(&self, index: usize) -> Result<&T, usize> {
+        self.iter().nth(index).ok_or_else(|| self.iter().count())
+    }
+}
+
+/* try_tail() under a name that says what it costs: the returned list holds a clone of
+ * the tail Rc, so it's O(1) and bumps the tail node's strong count by one rather than
+ * copying anything. tail() does exactly the same, it just hides the Option. */
+impl<T> List<T> {
+    pub fn tail_rc(&self) -> Option<List<T>> {
+        self.try_tail()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::List;
+    #[test]
+    fn basics() {
+        let list = List::new();
+        assert_eq!(list.head(), None);
+
+        let list = list.prepend(1).prepend(2).prepend(3);
+        assert_eq!(list.head(), Some(&3));
+
+        let list = list.tail();
+        assert_eq!(list.head(), Some(&2));
+
+        let list = list.tail();
+        assert_eq!(list.head(), Some(&1));
+
+        let list = list.tail();
+        assert_eq!(list.head(), None);
+
+        // Make sure empty tail works
+        let list = list.tail();
+        assert_eq!(list.head(), None);
+    }
+    #[test]
+    fn iter() {
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), None);
+    }
+    #[test]
+    fn count_where() {
+        let list = List::new().prepend(1).prepend(5).prepend(10);
+        assert_eq!(list.count_where(|&x| x > 3), 2);
+        assert_eq!(list.count_where(|&x| x > 100), 0);
+
+        let empty: List<i32> = List::new();
+        assert_eq!(empty.count_where(|_| true), 0);
+    }
+    #[test]
+    fn map() {
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+        let strings = list.map(|x| x.to_string());
+
+        assert_eq!(strings.head(), Some(&String::from("3")));
+        assert_eq!(strings.iter().collect::<Vec<_>>(), vec!["3", "2", "1"]);
+        // the source list is still there
+        assert_eq!(list.head(), Some(&3));
+    }
+    #[test]
+    fn filter() {
+        let list = List::new().prepend(4).prepend(3).prepend(2).prepend(1);
+        let evens = list.filter(|x| x % 2 == 0);
+
+        assert_eq!(evens.iter().collect::<Vec<_>>(), vec![&2, &4]);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+    }
+    #[test]
+    fn default() {
+        assert_eq!(List::<i32>::default().head(), None);
+    }
+    #[test]
+    fn fold() {
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+        assert_eq!(list.fold(0, |sum, x| sum + x), 6);
+        assert_eq!(list.fold(String::new(), |s, x| s + &x.to_string()), "321");
+    }
+    #[test]
+    fn for_loop() {
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+        let mut seen = Vec::new();
+        for x in &list {
+            seen.push(*x);
+        }
+        assert_eq!(seen, vec![3, 2, 1]);
+    }
+    #[test]
+    fn peek_mut() {
+        let mut list = List::new().prepend(1).prepend(2);
+        if let Some(value) = list.peek_mut() {
+            *value = 42;
+        }
+        assert_eq!(list.head(), Some(&42));
+
+        // once another list shares the head node, we can't mutate it
+        let mut tail = list.tail();
+        let _shared = list.prepend(0);
+        assert_eq!(list.peek_mut(), None);
+        assert_eq!(list.head(), Some(&42));
+
+        // but the tail's head is shared too (by list), so no luck there either
+        assert_eq!(tail.peek_mut(), None);
+    }
+    #[test]
+    fn prepend_many() {
+        let list = List::new().prepend(5).prepend(4);
+        let longer = list.prepend_many(&[1, 2, 3]);
+
+        assert_eq!(longer.iter().collect::<Vec<_>>(), vec![&3, &2, &1, &4, &5]);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&4, &5]);
+        assert_eq!(list.prepend_many(&[]).head(), Some(&4));
+    }
+    #[test]
+    fn try_tail() {
+        let empty: List<i32> = List::new();
+        assert!(empty.try_tail().is_none());
+
+        let list = List::new().prepend(1).prepend(2);
+        let tail = list.try_tail().unwrap();
+        assert_eq!(tail.head(), Some(&1));
+        let tail = tail.try_tail().unwrap();
+        assert_eq!(tail.head(), None);
+        assert!(tail.try_tail().is_none());
+    }
+    #[test]
+    fn long_list_drop() {
+        // a recursive drop would blow the stack long before a million nodes
+        let mut list = List::new();
+        for i in 0..1_000_000 {
+            list = list.prepend(i);
+        }
+        assert_eq!(list.head(), Some(&999_999));
+        drop(list);
+    }
+    #[test]
+    fn shared_suffix_len() {
+        let a = List::new().prepend(1).prepend(2).prepend(3);
+        let b = a.prepend(4);
+        assert_eq!(b.shared_suffix_len(&a), 3);
+        assert_eq!(a.shared_suffix_len(&b), 3);
+
+        let c = a.tail().prepend(5).prepend(6);
+        assert_eq!(c.shared_suffix_len(&b), 2);
+
+        // equal values, but separate nodes
+        let d = List::new().prepend(1).prepend(2).prepend(3);
+        assert_eq!(d.shared_suffix_len(&a), 0);
+        assert_eq!(List::new().shared_suffix_len(&a), 0);
+    }
+    #[test]
+    fn strong_count_at_head() {
+        assert_eq!(List::<i32>::new().strong_count_at_head(), 0);
+
+        let list = List::new().prepend(1).prepend(2);
+        assert_eq!(list.strong_count_at_head(), 1);
+
+        let copy = list.clone();
+        assert_eq!(list.strong_count_at_head(), 2);
+        let longer = list.prepend(3);
+        assert_eq!(list.strong_count_at_head(), 3);
+        assert_eq!(longer.strong_count_at_head(), 1);
+
+        drop(copy);
+        assert_eq!(list.strong_count_at_head(), 2);
+        drop(longer);
+        assert_eq!(list.strong_count_at_head(), 1);
+    }
+    #[test]
+    fn uncons() {
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+
+        let (head, rest) = list.uncons().unwrap();
+        assert_eq!(head, &3);
+        assert_eq!(rest.head(), Some(&2));
+
+        let (head, rest) = rest.uncons().unwrap();
+        assert_eq!(head, &2);
+        let (head, rest) = rest.uncons().unwrap();
+        assert_eq!(head, &1);
+        assert!(rest.uncons().is_none());
+    }
+    #[test]
+    fn from_iter_ordered() {
+        let list = List::from_iter_ordered(1..4);
+        assert_eq!(list.head(), Some(&1));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    }
+    #[test]
+    fn reverse_owned() {
+        // nothing shared: the very same nodes come back in reverse
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+        let old_head: *const i32 = list.head().unwrap();
+        let reversed = list.reverse_owned();
+        assert_eq!(reversed.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+        assert_eq!(reversed.iter().last().unwrap() as *const i32, old_head);
+
+        // 4 and 3 are ours, but 2 and 1 are shared with base and have to be cloned
+        let base = List::new().prepend(1).prepend(2);
+        let list = base.prepend(3).prepend(4);
+        let reversed = list.reverse_owned();
+        assert_eq!(reversed.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+        assert_eq!(base.iter().collect::<Vec<_>>(), vec![&2, &1]);
+        assert_eq!(reversed.shared_suffix_len(&base), 0);
+    }
+    #[test]
+    fn prepend_iter() {
+        let list = List::new().prepend(4).prepend(3);
+        let longer = list.prepend_iter(vec![1, 2]);
+        assert_eq!(longer.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+        assert_eq!(longer.shared_suffix_len(&list), 2);
+    }
+    #[test]
+    fn count_unique_nodes() {
+        let shared = List::new().prepend(3).prepend(2);
+        let a = shared.prepend(1);
+        let b = shared.prepend(10).prepend(11);
+        let lists = [&a, &b, &shared];
+        let total: usize = lists.iter().map(|list| list.iter().count()).sum();
+        assert_eq!(total, 9);
+        assert_eq!(List::count_unique_nodes(&lists), 5);
+        assert_eq!(List::<i32>::count_unique_nodes(&[]), 0);
+    }
+    #[test]
+    fn try_get() {
+        let list = List::from_iter_ordered(vec![1, 2, 3]);
+        assert_eq!(list.try_get(0), Ok(&1));
+        assert_eq!(list.try_get(2), Ok(&3));
+        assert_eq!(list.try_get(3), Err(3));
+        assert_eq!(list.try_get(100), Err(3));
+        assert_eq!(List::<i32>::new().try_get(0), Err(0));
+    }
+    #[test]
+    fn tail_rc() {
+        let list = List::from_iter_ordered(vec![1, 2, 3]);
+        assert_eq!(list.tail().strong_count_at_head(), 2);
+        let tail = list.tail_rc().unwrap();
+        assert_eq!(tail.strong_count_at_head(), 2);
+        let again = list.tail_rc().unwrap();
+        assert_eq!(tail.strong_count_at_head(), 3);
+        assert_eq!(again.shared_suffix_len(&list), 2);
+        drop(again);
+        assert_eq!(tail.strong_count_at_head(), 2);
+        assert!(List::<i32>::new().tail_rc().is_none());
+    }
+}